@@ -0,0 +1,79 @@
+use domain::{post::Id as PostId, user::Id as UserId};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "app", about = "Administer the users and posts of the blog")]
+pub struct Cli {
+    /// Output format: `table` or `json`
+    #[structopt(long, global = true, default_value = "table")]
+    pub format: Format,
+
+    #[structopt(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Table,
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown format \"{}\"", value)),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Manage the users
+    User(UserCommand),
+    /// Manage the posts
+    Post(PostCommand),
+    /// Apply the pending migrations of the storage backend
+    Migrate,
+    /// Create a few users with a post each, skipping the nicknames already taken
+    Seed,
+}
+
+#[derive(Debug, StructOpt)]
+pub enum UserCommand {
+    /// Create a user, without password
+    Create { nickname: String },
+    /// Show a user
+    Get { id: UserId },
+    /// Show the user with a nickname, regardless of case
+    Find { nickname: String },
+    /// Rename a user
+    Rename {
+        id: UserId,
+        nickname: String,
+        /// Fail if the user is no longer at this version
+        #[structopt(long)]
+        version: Option<i32>,
+    },
+    /// Soft delete a user
+    Delete { id: UserId },
+}
+
+#[derive(Debug, StructOpt)]
+pub enum PostCommand {
+    /// Create a post
+    Create { author_id: UserId, content: String },
+    /// Show a post
+    Get { id: PostId },
+    /// List the posts, ordered by id
+    List {
+        /// Only list the posts after this one
+        #[structopt(long)]
+        after: Option<PostId>,
+        #[structopt(long, default_value = "20")]
+        limit: usize,
+    },
+}
@@ -0,0 +1,135 @@
+use crate::{
+    cli::{Command, Format, PostCommand, UserCommand},
+    errors,
+    output::{self, PostView, UserView},
+    AppModule,
+};
+use domain::{
+    libs::id::Identifier,
+    policies::Actor,
+    post::RepositoryInterface as PostRepositoryInterface,
+    use_cases::{
+        create_post::Interface as CreatePostUseCase,
+        create_user::Interface as CreateUserUseCase,
+        create_user_with_post::{self, Interface as CreateUserWithPostUseCase},
+        delete_user::Interface as DeleteUserUseCase,
+        rename_user::Interface as RenameUserUseCase,
+    },
+    user::{self, RepositoryInterface as UserRepositoryInterface, Role},
+};
+use shaku::HasProvider;
+
+/// Users and posts created by the `seed` command.
+const SEED: &[(&str, &str)] = &[
+    ("Elliot", "Hello, friend."),
+    ("Darlene", "We are *fsociety*."),
+    ("Angela", "Control is an illusion."),
+];
+
+/// The CLI is run by the operators, who act as an admin.
+fn operator() -> Actor {
+    Actor::new(user::Id::new(0), Role::Admin)
+}
+
+fn provide<I: ?Sized + 'static>(module: &AppModule) -> Result<Box<I>, errors::Command>
+where
+    AppModule: HasProvider<I>,
+{
+    module
+        .provide()
+        .map_err(|err| errors::Command::Other(anyhow::anyhow!(err.to_string())))
+}
+
+pub async fn run(
+    module: &AppModule,
+    format: Format,
+    command: Command,
+) -> Result<(), errors::Command> {
+    match command {
+        Command::User(command) => user(module, format, command).await,
+        Command::Post(command) => post(module, format, command).await,
+        Command::Seed => seed(module, format).await,
+        // Migrations are applied before the module is built, see `main`.
+        Command::Migrate => Ok(()),
+    }
+}
+
+async fn user(
+    module: &AppModule,
+    format: Format,
+    command: UserCommand,
+) -> Result<(), errors::Command> {
+    let user = match command {
+        UserCommand::Create { nickname } => {
+            let create_user: Box<dyn CreateUserUseCase> = provide(module)?;
+            create_user.call(&nickname).await?
+        }
+        UserCommand::Get { id } => {
+            let repository: Box<dyn UserRepositoryInterface + Send + Sync> = provide(module)?;
+            repository.find(&id).await?
+        }
+        UserCommand::Find { nickname } => {
+            let repository: Box<dyn UserRepositoryInterface + Send + Sync> = provide(module)?;
+            repository.find_by_nickname(&nickname).await?
+        }
+        UserCommand::Rename {
+            id,
+            nickname,
+            version,
+        } => {
+            let rename_user: Box<dyn RenameUserUseCase> = provide(module)?;
+            rename_user
+                .call(&operator(), &id, &nickname, version)
+                .await?
+        }
+        UserCommand::Delete { id } => {
+            let delete_user: Box<dyn DeleteUserUseCase> = provide(module)?;
+            delete_user.call(&operator(), &id).await?;
+            let repository: Box<dyn UserRepositoryInterface + Send + Sync> = provide(module)?;
+            repository.find_including_deleted(&id).await?
+        }
+    };
+
+    Ok(output::print_one(format, UserView::from(&user))?)
+}
+
+async fn post(
+    module: &AppModule,
+    format: Format,
+    command: PostCommand,
+) -> Result<(), errors::Command> {
+    match command {
+        PostCommand::Create { author_id, content } => {
+            let create_post: Box<dyn CreatePostUseCase> = provide(module)?;
+            let post = create_post.call(&author_id, &content).await?;
+            Ok(output::print_one(format, PostView::from(&post))?)
+        }
+        PostCommand::Get { id } => {
+            let repository: Box<dyn PostRepositoryInterface + Send + Sync> = provide(module)?;
+            let post = repository.find(&id).await?;
+            Ok(output::print_one(format, PostView::from(&post))?)
+        }
+        PostCommand::List { after, limit } => {
+            let repository: Box<dyn PostRepositoryInterface + Send + Sync> = provide(module)?;
+            let page = repository.list(after.as_ref(), limit).await?;
+            let posts: Vec<PostView> = page.items.iter().map(PostView::from).collect();
+            Ok(output::print(format, &posts)?)
+        }
+    }
+}
+
+async fn seed(module: &AppModule, format: Format) -> Result<(), errors::Command> {
+    let create_user_with_post: Box<dyn CreateUserWithPostUseCase> = provide(module)?;
+    let mut users = vec![];
+    for (nickname, content) in SEED {
+        match create_user_with_post.call(nickname, content).await {
+            Ok((user, _)) => users.push(UserView::from(&user)),
+            Err(create_user_with_post::errors::Create::User(
+                user::errors::Save::NicknameExists { .. },
+            )) => eprintln!("Skipped \"{}\", the nickname is already taken", nickname),
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(output::print(format, &users)?)
+}
@@ -0,0 +1,127 @@
+use domain::{
+    post,
+    use_cases::{create_post, create_user, create_user_with_post, delete_user, rename_user},
+    user,
+};
+use thiserror::Error;
+
+/// Why a command failed, each reason having its own exit code so that scripts can tell them apart.
+#[derive(Error, Debug)]
+pub enum Command {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    NicknameExists(String),
+
+    #[error("{0}")]
+    Invalid(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl Command {
+    /// `2` is left to the usage errors reported by the argument parser.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Command::Other(_) => 1,
+            Command::NotFound(_) => 3,
+            Command::NicknameExists(_) => 4,
+            Command::Invalid(_) => 5,
+            Command::Conflict(_) => 6,
+        }
+    }
+}
+
+impl From<user::errors::Find> for Command {
+    fn from(err: user::errors::Find) -> Self {
+        match err {
+            user::errors::Find::NotFound => Command::NotFound(err.to_string()),
+            user::errors::Find::Other(err) => Command::Other(err),
+        }
+    }
+}
+
+impl From<post::errors::Find> for Command {
+    fn from(err: post::errors::Find) -> Self {
+        match err {
+            post::errors::Find::NotFound => Command::NotFound(err.to_string()),
+            post::errors::Find::Other(err) => Command::Other(err),
+        }
+    }
+}
+
+impl From<post::errors::List> for Command {
+    fn from(err: post::errors::List) -> Self {
+        match err {
+            post::errors::List::Other(err) => Command::Other(err),
+        }
+    }
+}
+
+impl From<create_user::errors::Create> for Command {
+    fn from(err: create_user::errors::Create) -> Self {
+        match err {
+            create_user::errors::Create::InvalidNickname(_) => Command::Invalid(err.to_string()),
+            create_user::errors::Create::NicknameExists { .. } => {
+                Command::NicknameExists(err.to_string())
+            }
+            create_user::errors::Create::Other(err) => Command::Other(err),
+        }
+    }
+}
+
+impl From<rename_user::errors::Rename> for Command {
+    fn from(err: rename_user::errors::Rename) -> Self {
+        match err {
+            rename_user::errors::Rename::NotFound => Command::NotFound(err.to_string()),
+            rename_user::errors::Rename::InvalidNickname(_) => Command::Invalid(err.to_string()),
+            rename_user::errors::Rename::NicknameExists { .. } => {
+                Command::NicknameExists(err.to_string())
+            }
+            rename_user::errors::Rename::Conflict => Command::Conflict(err.to_string()),
+            rename_user::errors::Rename::Forbidden(err) => Command::Other(err.into()),
+            rename_user::errors::Rename::Other(err) => Command::Other(err),
+        }
+    }
+}
+
+impl From<delete_user::errors::Delete> for Command {
+    fn from(err: delete_user::errors::Delete) -> Self {
+        match err {
+            delete_user::errors::Delete::NotFound => Command::NotFound(err.to_string()),
+            delete_user::errors::Delete::Conflict => Command::Conflict(err.to_string()),
+            delete_user::errors::Delete::Forbidden(err) => Command::Other(err.into()),
+            delete_user::errors::Delete::Other(err) => Command::Other(err),
+        }
+    }
+}
+
+impl From<create_post::errors::Create> for Command {
+    fn from(err: create_post::errors::Create) -> Self {
+        match err {
+            create_post::errors::Create::AuthorNotFound => Command::NotFound(err.to_string()),
+            create_post::errors::Create::InvalidContent(_) => Command::Invalid(err.to_string()),
+            create_post::errors::Create::Other(err) => Command::Other(err),
+        }
+    }
+}
+
+impl From<create_user_with_post::errors::Create> for Command {
+    fn from(err: create_user_with_post::errors::Create) -> Self {
+        match err {
+            create_user_with_post::errors::Create::InvalidNickname(_)
+            | create_user_with_post::errors::Create::InvalidContent(_) => {
+                Command::Invalid(err.to_string())
+            }
+            create_user_with_post::errors::Create::User(user::errors::Save::NicknameExists {
+                ..
+            }) => Command::NicknameExists(err.to_string()),
+            err => Command::Other(err.into()),
+        }
+    }
+}
@@ -1,32 +1,72 @@
-use domain::use_cases::create_user::{
-    Interface as CreateUserUseCase, UseCase as CreateUserUseCaseImpl,
+use anyhow::Context;
+use cli::{Cli, Command};
+use domain::use_cases::{
+    create_post::UseCase as CreatePostUseCaseImpl, create_user::UseCase as CreateUserUseCaseImpl,
+    create_user_with_post::UseCase as CreateUserWithPostUseCaseImpl,
+    delete_user::UseCase as DeleteUserUseCaseImpl, rename_user::UseCase as RenameUserUseCaseImpl,
 };
 use infrastructure::{
-    libs::db::service::DBService, user::db_repository::DbRepository as UserDbRepository,
+    backend::{self, PostRepository, Store, UnitOfWork, UserRepository},
+    config::Config,
+    events::dispatcher::InProcessDispatcher,
+    libs::clock::SystemClock,
 };
-use shaku::{module, HasProvider};
+use shaku::module;
+use structopt::StructOpt;
+
+mod cli;
+mod commands;
+mod errors;
+mod output;
 
 // Module
 module! {
     AppModule {
-        components = [],
+        components = [
+            Store,
+            InProcessDispatcher,
+            SystemClock,
+        ],
         providers = [
-            UserDbRepository,
+            UserRepository,
+            PostRepository,
+            UnitOfWork,
             CreateUserUseCaseImpl,
-            DBService,
+            RenameUserUseCaseImpl,
+            DeleteUserUseCaseImpl,
+            CreatePostUseCaseImpl,
+            CreateUserWithPostUseCaseImpl,
         ]
     }
 }
 
-#[tokio::main]
-pub async fn main() -> Result<(), ()> {
-    let module = AppModule::builder().build();
-    let create_user: Box<dyn CreateUserUseCase> = module.provide().unwrap();
-    let user = create_user.call("Mathieu").await;
-    println!("name: {:#?}", user);
-    if let Err(err) = user {
-        println!("err: {}", err);
+async fn run(cli: Cli) -> Result<(), errors::Command> {
+    let mut config = Config::load().map_err(anyhow::Error::from)?;
+    if let Command::Migrate = cli.command {
+        // Applied below rather than while connecting, so that the command reports them.
+        config.database.run_migrations = false;
+    }
+    let store = backend::connect(&config.database)
+        .await
+        .context("Failed to connect to the database")?;
+    if let Command::Migrate = cli.command {
+        backend::run_migrations(&store)
+            .await
+            .context("Failed to apply the migrations")?;
+        println!("Migrations applied");
+        return Ok(());
     }
 
-    Ok(())
+    let module = AppModule::builder()
+        .with_component_parameters::<Store>(store)
+        .build();
+    commands::run(&module, cli.format, cli.command).await
+}
+
+#[tokio::main]
+pub async fn main() {
+    if let Err(err) = run(Cli::from_args()).await {
+        eprintln!("Error: {:#}", err);
+        std::process::exit(err.exit_code());
+    }
 }
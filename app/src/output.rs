@@ -0,0 +1,169 @@
+use crate::cli::Format;
+use chrono::{DateTime, Utc};
+use domain::{post::Post, user::User};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct UserView {
+    id: String,
+    nickname: String,
+    role: &'static str,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl From<&User> for UserView {
+    fn from(user: &User) -> Self {
+        UserView {
+            id: user.id().to_string(),
+            nickname: user.nickname().into(),
+            role: user.role().as_str(),
+            created_at: *user.created_at(),
+            updated_at: *user.updated_at(),
+            version: user.version(),
+            deleted_at: user.deleted_at().copied(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PostView {
+    id: String,
+    author_id: String,
+    content: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
+}
+
+impl From<&Post> for PostView {
+    fn from(post: &Post) -> Self {
+        PostView {
+            id: post.id().to_string(),
+            author_id: post.author_id().to_string(),
+            content: post.content().into(),
+            created_at: *post.created_at(),
+            updated_at: *post.updated_at(),
+            version: post.version(),
+        }
+    }
+}
+
+/// What a view shows as a table row.
+pub trait Row {
+    fn headers() -> &'static [&'static str];
+    fn cells(&self) -> Vec<String>;
+}
+
+impl Row for UserView {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "ID",
+            "NICKNAME",
+            "ROLE",
+            "CREATED AT",
+            "VERSION",
+            "DELETED AT",
+        ]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.nickname.clone(),
+            self.role.into(),
+            self.created_at.to_rfc3339(),
+            self.version.to_string(),
+            self.deleted_at
+                .map(|deleted_at| deleted_at.to_rfc3339())
+                .unwrap_or_default(),
+        ]
+    }
+}
+
+impl Row for PostView {
+    fn headers() -> &'static [&'static str] {
+        &["ID", "AUTHOR ID", "CONTENT", "CREATED AT", "VERSION"]
+    }
+
+    /// Only the first line of the content, so that a post fits in a row.
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.author_id.clone(),
+            self.content.lines().next().unwrap_or_default().into(),
+            self.created_at.to_rfc3339(),
+            self.version.to_string(),
+        ]
+    }
+}
+
+pub fn print<T: Serialize + Row>(format: Format, rows: &[T]) -> Result<(), anyhow::Error> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(rows)?),
+        Format::Table => print!("{}", table(rows)),
+    }
+    Ok(())
+}
+
+pub fn print_one<T: Serialize + Row>(format: Format, row: T) -> Result<(), anyhow::Error> {
+    match format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&row)?),
+        Format::Table => print!("{}", table(&[row])),
+    }
+    Ok(())
+}
+
+fn table<T: Row>(rows: &[T]) -> String {
+    let headers: Vec<String> = T::headers().iter().map(|&header| header.into()).collect();
+    let rows: Vec<Vec<String>> = std::iter::once(headers)
+        .chain(rows.iter().map(T::cells))
+        .collect();
+    let widths: Vec<usize> = (0..T::headers().len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<String>>()
+                .join("  ");
+            format!("{}\n", line.trim_end())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{table, Row};
+
+    struct Fruit(&'static str, u32);
+
+    impl Row for Fruit {
+        fn headers() -> &'static [&'static str] {
+            &["NAME", "COUNT"]
+        }
+
+        fn cells(&self) -> Vec<String> {
+            vec![self.0.into(), self.1.to_string()]
+        }
+    }
+
+    #[test]
+    fn it_aligns_the_columns() {
+        assert_eq!(
+            table(&[Fruit("Apple", 3), Fruit("Kiwi", 12)]),
+            "NAME   COUNT\nApple  3\nKiwi   12\n"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use super::user::{self, User};
+use async_trait::async_trait;
+use shaku::Interface;
+
+pub const MIN_PASSWORD_LENGTH: usize = 8;
+
+pub mod errors {
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Token {
+        #[error("Invalid or expired token")]
+        Invalid,
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+}
+
+/// An authenticated user with the token to send back on the next requests.
+#[derive(Debug)]
+pub struct Session {
+    pub user: User,
+    pub token: String,
+}
+
+/// Hashing is slow on purpose, implementations must not block the executor while doing it.
+#[async_trait]
+pub trait PasswordHasherInterface: Interface {
+    async fn hash(&self, password: &str) -> Result<String, anyhow::Error>;
+    async fn verify(&self, password_hash: &str, password: &str) -> Result<bool, anyhow::Error>;
+    /// A hash of no one's password, as costly to verify as a real one.
+    fn dummy_hash(&self) -> &str;
+}
+
+pub trait TokenServiceInterface: Interface {
+    fn issue(&self, user_id: &user::Id) -> Result<String, anyhow::Error>;
+    fn verify(&self, token: &str) -> Result<user::Id, errors::Token>;
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::{errors, user, PasswordHasherInterface, TokenServiceInterface};
+    use async_trait::async_trait;
+
+    /// Hasher prefixing the passwords instead of hashing them.
+    pub struct FakePasswordHasher;
+
+    #[async_trait]
+    impl PasswordHasherInterface for FakePasswordHasher {
+        async fn hash(&self, password: &str) -> Result<String, anyhow::Error> {
+            Ok(format!("hashed:{}", password))
+        }
+
+        async fn verify(&self, password_hash: &str, password: &str) -> Result<bool, anyhow::Error> {
+            Ok(password_hash == format!("hashed:{}", password))
+        }
+
+        fn dummy_hash(&self) -> &str {
+            "hashed:dummy"
+        }
+    }
+
+    /// Token service whose tokens are the prefixed id of the user.
+    pub struct FakeTokenService;
+
+    impl TokenServiceInterface for FakeTokenService {
+        fn issue(&self, user_id: &user::Id) -> Result<String, anyhow::Error> {
+            Ok(format!("token:{}", user_id))
+        }
+
+        fn verify(&self, token: &str) -> Result<user::Id, errors::Token> {
+            token
+                .strip_prefix("token:")
+                .and_then(|user_id| user_id.parse().ok())
+                .ok_or(errors::Token::Invalid)
+        }
+    }
+}
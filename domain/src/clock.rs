@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use shaku::Interface;
+
+/// Source of the current time, injected so that tests can freeze it.
+pub trait ClockInterface: Interface {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::ClockInterface;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    /// Clock always returning the same instant.
+    pub struct FixedClock(pub DateTime<Utc>);
+
+    impl Default for FixedClock {
+        fn default() -> Self {
+            FixedClock(Utc.ymd(2021, 2, 21).and_hms(10, 0, 0))
+        }
+    }
+
+    impl ClockInterface for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use super::{post, user};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shaku::Interface;
+use std::sync::Arc;
+
+/// Something that happened in the domain, recorded by the entities and published once persisted.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DomainEvent {
+    UserCreated {
+        id: user::Id,
+        nickname: String,
+        created_at: DateTime<Utc>,
+    },
+    PostCreated {
+        id: post::Id,
+        author_id: user::Id,
+        content: String,
+        created_at: DateTime<Utc>,
+    },
+}
+
+impl DomainEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::UserCreated { .. } => "UserCreated",
+            DomainEvent::PostCreated { .. } => "PostCreated",
+        }
+    }
+}
+
+#[async_trait]
+pub trait EventHandler: Send + Sync {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), anyhow::Error>;
+}
+
+#[async_trait]
+pub trait DispatcherInterface: Interface {
+    fn subscribe(&self, handler: Arc<dyn EventHandler>);
+
+    /// Hand the events to every subscribed handler, a failing handler does not stop the others.
+    async fn dispatch(&self, events: Vec<DomainEvent>);
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::{DispatcherInterface, DomainEvent, EventHandler};
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    /// Dispatcher recording the events instead of handing them to handlers.
+    #[derive(Default)]
+    pub struct FakeDispatcher {
+        pub dispatched: Mutex<Vec<DomainEvent>>,
+    }
+
+    #[async_trait]
+    impl DispatcherInterface for FakeDispatcher {
+        fn subscribe(&self, _handler: Arc<dyn EventHandler>) {}
+
+        async fn dispatch(&self, events: Vec<DomainEvent>) {
+            self.dispatched.lock().unwrap().extend(events);
+        }
+    }
+}
@@ -1,4 +1,9 @@
+pub mod auth;
+pub mod clock;
+pub mod events;
 pub mod libs;
+pub mod policies;
 pub mod post;
+pub mod unit_of_work;
 pub mod use_cases;
 pub mod user;
@@ -0,0 +1,18 @@
+/// A slice of a listing, items are ordered by id.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub has_next_page: bool,
+}
+
+impl<T> Page<T> {
+    /// Build a page from up to `limit + 1` items, the extra one only tells if there is a next page.
+    pub fn from_items(mut items: Vec<T>, limit: usize) -> Page<T> {
+        let has_next_page = items.len() > limit;
+        items.truncate(limit);
+        Page {
+            items,
+            has_next_page,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Authorization rules, use cases check them before touching the repositories.
+
+use super::{
+    post::Post,
+    user::{self, Role, User},
+};
+use thiserror::Error;
+
+/// The user on whose behalf a use case is performed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Actor {
+    id: user::Id,
+    role: Role,
+}
+
+impl Actor {
+    pub fn new(id: user::Id, role: Role) -> Actor {
+        Actor { id, role }
+    }
+
+    pub fn from_user(user: &User) -> Actor {
+        Actor::new(*user.id(), user.role())
+    }
+
+    pub fn id(&self) -> &user::Id {
+        &self.id
+    }
+
+    pub fn is_admin(&self) -> bool {
+        self.role == Role::Admin
+    }
+}
+
+#[derive(Error, Debug, Eq, PartialEq)]
+#[error("You are not allowed to {action}")]
+pub struct Forbidden {
+    pub action: &'static str,
+}
+
+fn allow_if(allowed: bool, action: &'static str) -> Result<(), Forbidden> {
+    if allowed {
+        Ok(())
+    } else {
+        Err(Forbidden { action })
+    }
+}
+
+/// Only the author can edit a post.
+pub fn can_edit_post(actor: &Actor, post: &Post) -> Result<(), Forbidden> {
+    allow_if(actor.id() == post.author_id(), "edit this post")
+}
+
+/// Only the author can delete a post.
+pub fn can_delete_post(actor: &Actor, post: &Post) -> Result<(), Forbidden> {
+    allow_if(actor.id() == post.author_id(), "delete this post")
+}
+
+/// Users can rename themselves, admins can rename anyone.
+pub fn can_rename_user(actor: &Actor, user_id: &user::Id) -> Result<(), Forbidden> {
+    allow_if(
+        actor.is_admin() || actor.id() == user_id,
+        "rename this user",
+    )
+}
+
+/// Users can delete their own account, admins can delete any user.
+pub fn can_delete_user(actor: &Actor, user_id: &user::Id) -> Result<(), Forbidden> {
+    allow_if(
+        actor.is_admin() || actor.id() == user_id,
+        "delete this user",
+    )
+}
+
+/// Only admins can restore a deleted user, as part of moderation.
+pub fn can_restore_user(actor: &Actor) -> Result<(), Forbidden> {
+    allow_if(actor.is_admin(), "restore this user")
+}
+
+/// Only admins can restore a deleted post, as part of moderation.
+pub fn can_restore_post(actor: &Actor) -> Result<(), Forbidden> {
+    allow_if(actor.is_admin(), "restore this post")
+}
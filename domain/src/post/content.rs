@@ -0,0 +1,82 @@
+pub const MAX_LENGTH: usize = 10_000;
+
+pub mod errors {
+    use thiserror::Error;
+
+    #[derive(Error, Debug, Eq, PartialEq)]
+    pub enum Invalid {
+        #[error("Content cannot be empty")]
+        Empty,
+
+        #[error("Content must be at most {max_length} characters long")]
+        TooLong { max_length: usize },
+    }
+}
+
+/// The Markdown body of a post, rendered to HTML by a `RendererInterface` when read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PostContent(String);
+
+impl PostContent {
+    pub fn parse(value: &str) -> Result<PostContent, errors::Invalid> {
+        if value.trim().is_empty() {
+            return Err(errors::Invalid::Empty);
+        }
+        if value.chars().count() > MAX_LENGTH {
+            return Err(errors::Invalid::TooLong {
+                max_length: MAX_LENGTH,
+            });
+        }
+
+        Ok(PostContent(value.to_owned()))
+    }
+
+    /// Contents loaded from the repository were validated when they were saved.
+    pub(super) fn build_from_repository(content: String) -> PostContent {
+        PostContent(content)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::str::FromStr for PostContent {
+    type Err = errors::Invalid;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        PostContent::parse(value)
+    }
+}
+
+impl std::fmt::Display for PostContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{errors::Invalid, PostContent, MAX_LENGTH};
+
+    #[test]
+    fn it_accepts_markdown_content() {
+        let content = PostContent::parse("# Hello\n\n*friend*").unwrap();
+        assert_eq!(content.as_str(), "# Hello\n\n*friend*");
+    }
+
+    #[test]
+    fn it_rejects_blank_content() {
+        assert_eq!(PostContent::parse(" \n\t"), Err(Invalid::Empty));
+    }
+
+    #[test]
+    fn it_rejects_content_too_long() {
+        assert_eq!(
+            PostContent::parse(&"a".repeat(MAX_LENGTH + 1)),
+            Err(Invalid::TooLong {
+                max_length: MAX_LENGTH
+            })
+        );
+    }
+}
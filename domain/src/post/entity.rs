@@ -1,32 +1,135 @@
+use super::content::PostContent;
+use crate::events::DomainEvent;
 use crate::libs::id::{uuid, Display, FromStr, Identifier};
+use crate::user;
+use chrono::{DateTime, Utc};
 
-#[derive(Identifier, Eq, PartialEq, Display, FromStr, Debug)]
+#[derive(Identifier, Clone, Copy, Eq, PartialEq, Display, FromStr, Debug)]
 #[identifier(with = "uuid")]
 pub struct Id(u128);
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Post {
     id: Id,
-    content: String,
+    author_id: user::Id,
+    content: PostContent,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
+    loaded_version: i32,
+    deleted_at: Option<DateTime<Utc>>,
+    events: Vec<DomainEvent>,
 }
 
 impl Post {
-    pub fn new(content: &str) -> Post {
+    pub fn new(author_id: user::Id, content: PostContent, now: DateTime<Utc>) -> Post {
+        let id = Id::generate();
         Post {
-            id: Id::generate(),
-            content: content.to_owned(),
+            id,
+            author_id,
+            events: vec![DomainEvent::PostCreated {
+                id,
+                author_id,
+                content: content.to_string(),
+                created_at: now,
+            }],
+            content,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            loaded_version: 1,
+            deleted_at: None,
         }
     }
 
-    pub fn build_from_repository(id: Id, content: String) -> Post {
-        Post { id, content }
+    pub fn build_from_repository(
+        id: Id,
+        author_id: user::Id,
+        content: String,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+        version: i32,
+        deleted_at: Option<DateTime<Utc>>,
+    ) -> Post {
+        Post {
+            id,
+            author_id,
+            content: PostContent::build_from_repository(content),
+            created_at,
+            updated_at,
+            version,
+            loaded_version: version,
+            deleted_at,
+            events: vec![],
+        }
     }
 
     pub fn id(&self) -> &Id {
         &self.id
     }
 
+    pub fn author_id(&self) -> &user::Id {
+        &self.author_id
+    }
+
+    /// The Markdown source of the post.
     pub fn content(&self) -> &str {
-        &self.content
+        self.content.as_str()
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    /// Incremented when the post changes, so that concurrent writers can be detected.
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// Version the post had when loaded, a repository only overwrites a post still at this version.
+    pub fn loaded_version(&self) -> i32 {
+        self.loaded_version
+    }
+
+    /// Soft deleted posts are hidden by the repositories but can be restored.
+    pub fn deleted_at(&self) -> Option<&DateTime<Utc>> {
+        self.deleted_at.as_ref()
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub fn delete(&mut self, now: DateTime<Utc>) {
+        self.deleted_at = Some(now);
+        self.touch(now);
+    }
+
+    pub fn restore(&mut self, now: DateTime<Utc>) {
+        self.deleted_at = None;
+        self.touch(now);
+    }
+
+    fn touch(&mut self, now: DateTime<Utc>) {
+        self.updated_at = now;
+        self.version = self.loaded_version + 1;
+    }
+
+    pub fn edit(&mut self, content: PostContent, now: DateTime<Utc>) {
+        self.content = content;
+        self.touch(now);
+    }
+
+    /// Events recorded since the post was loaded or created, not yet published.
+    pub fn events(&self) -> &[DomainEvent] {
+        &self.events
+    }
+
+    pub fn take_events(&mut self) -> Vec<DomainEvent> {
+        std::mem::take(&mut self.events)
     }
 }
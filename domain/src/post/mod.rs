@@ -1,5 +1,108 @@
+pub mod content;
 mod entity;
+mod renderer;
 mod repository;
 
+pub use content::PostContent;
 pub use entity::*;
+pub use renderer::*;
 pub use repository::*;
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::{errors, Id, Post, RepositoryInterface};
+    use crate::libs::pagination::Page;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    /// Repository keeping the posts in memory for the use case tests. Like the real ones it hides
+    /// the soft deleted posts and detects concurrent updates, but it does not check the authors.
+    /// Its clones share the posts, so that a test can look at what a use case wrote.
+    #[derive(Clone, Default)]
+    pub struct FakeRepository {
+        posts: Arc<Mutex<Vec<Post>>>,
+    }
+
+    /// Copy a post as a repository would load it, without its pending events.
+    fn stored(post: &Post) -> Post {
+        Post::build_from_repository(
+            *post.id(),
+            *post.author_id(),
+            post.content().into(),
+            *post.created_at(),
+            *post.updated_at(),
+            post.version(),
+            post.deleted_at().copied(),
+        )
+    }
+
+    impl FakeRepository {
+        pub fn with(posts: &[Post]) -> FakeRepository {
+            FakeRepository {
+                posts: Arc::new(Mutex::new(posts.iter().map(stored).collect())),
+            }
+        }
+
+        /// The post as it is stored, soft deleted or not.
+        pub fn get(&self, id: &Id) -> Option<Post> {
+            let posts = self.posts.lock().unwrap();
+            posts.iter().find(|post| post.id() == id).map(stored)
+        }
+
+        pub fn count(&self) -> usize {
+            self.posts.lock().unwrap().len()
+        }
+
+        fn find_where(&self, predicate: impl Fn(&Post) -> bool) -> Result<Post, errors::Find> {
+            let posts = self.posts.lock().unwrap();
+            posts
+                .iter()
+                .find(|post| predicate(post))
+                .map(stored)
+                .ok_or(errors::Find::NotFound)
+        }
+    }
+
+    #[async_trait]
+    impl RepositoryInterface for FakeRepository {
+        async fn find(&self, id: &Id) -> Result<Post, errors::Find> {
+            self.find_where(|post| post.id() == id && !post.is_deleted())
+        }
+
+        async fn find_including_deleted(&self, id: &Id) -> Result<Post, errors::Find> {
+            self.find_where(|post| post.id() == id)
+        }
+
+        async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<Post>, errors::List> {
+            let posts = self.posts.lock().unwrap();
+            let start = after
+                .and_then(|after| posts.iter().position(|post| post.id() == after))
+                .map_or(0, |position| position + 1);
+            let items = posts[start..]
+                .iter()
+                .filter(|post| !post.is_deleted())
+                .take(limit + 1)
+                .map(stored)
+                .collect();
+            Ok(Page::from_items(items, limit))
+        }
+
+        async fn save(&self, post: &Post) -> Result<(), errors::Save> {
+            self.posts.lock().unwrap().push(stored(post));
+            Ok(())
+        }
+
+        async fn update(&self, post: &Post) -> Result<(), errors::Update> {
+            let mut posts = self.posts.lock().unwrap();
+            let position = posts
+                .iter()
+                .position(|other| other.id() == post.id())
+                .ok_or(errors::Update::NotFound)?;
+            if posts[position].version() != post.loaded_version() {
+                return Err(errors::Update::Conflict);
+            }
+            posts[position] = stored(post);
+            Ok(())
+        }
+    }
+}
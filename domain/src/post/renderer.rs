@@ -0,0 +1,6 @@
+use shaku::Interface;
+
+pub trait RendererInterface: Interface {
+    /// Render the Markdown content of a post to HTML safe to embed in a page.
+    fn render_html(&self, content: &str) -> String;
+}
@@ -1,4 +1,5 @@
 use super::entity::{Id, Post};
+use crate::libs::pagination::Page;
 use async_trait::async_trait;
 
 pub mod errors {
@@ -13,8 +14,29 @@ pub mod errors {
         Other(#[from] anyhow::Error),
     }
 
+    #[derive(Error, Debug)]
+    pub enum List {
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
     #[derive(Error, Debug)]
     pub enum Save {
+        #[error("Author not found")]
+        AuthorNotFound,
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    #[derive(Error, Debug)]
+    pub enum Update {
+        #[error("Post not found")]
+        NotFound,
+
+        #[error("Post was modified by someone else")]
+        Conflict,
+
         #[error(transparent)]
         Other(#[from] anyhow::Error),
     }
@@ -22,6 +44,10 @@ pub mod errors {
 
 #[async_trait]
 pub trait RepositoryInterface: Send + Sync {
+    /// Soft deleted posts are not found, use `find_including_deleted` to see them.
     async fn find(&self, id: &Id) -> Result<Post, errors::Find>;
+    async fn find_including_deleted(&self, id: &Id) -> Result<Post, errors::Find>;
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<Post>, errors::List>;
     async fn save(&self, user: &Post) -> Result<(), errors::Save>;
+    async fn update(&self, post: &Post) -> Result<(), errors::Update>;
 }
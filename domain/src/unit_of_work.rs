@@ -0,0 +1,40 @@
+use super::{post, user};
+use async_trait::async_trait;
+
+pub mod errors {
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Begin {
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    #[derive(Error, Debug)]
+    pub enum Commit {
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    #[derive(Error, Debug)]
+    pub enum Rollback {
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+}
+
+/// Writes done through the repositories of a transaction are only applied once it is committed.
+///
+/// Dropping a transaction without committing it discards every write.
+#[async_trait]
+pub trait Transaction: Send + Sync {
+    fn users(&self) -> &dyn user::RepositoryInterface;
+    fn posts(&self) -> &dyn post::RepositoryInterface;
+    async fn commit(self: Box<Self>) -> Result<(), errors::Commit>;
+    async fn rollback(self: Box<Self>) -> Result<(), errors::Rollback>;
+}
+
+#[async_trait]
+pub trait UnitOfWorkInterface: Send + Sync {
+    async fn begin(&self) -> Result<Box<dyn Transaction>, errors::Begin>;
+}
@@ -1,18 +1,56 @@
 use async_trait::async_trait;
 use shaku::Provider;
+use std::sync::Arc;
 
-use super::super::post::{errors, Post, RepositoryInterface};
+use super::super::{
+    clock::ClockInterface,
+    events::DispatcherInterface,
+    post::{self, Post, PostContent},
+    user,
+};
+
+pub mod errors {
+    use super::post;
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Create {
+        #[error("Author not found")]
+        AuthorNotFound,
+
+        #[error(transparent)]
+        InvalidContent(#[from] post::content::errors::Invalid),
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<post::errors::Save> for Create {
+        fn from(err: post::errors::Save) -> Self {
+            match err {
+                post::errors::Save::AuthorNotFound => Create::AuthorNotFound,
+                post::errors::Save::Other(err) => Create::Other(err),
+            }
+        }
+    }
+}
 
 #[async_trait]
 pub trait Interface: Send + Sync {
-    async fn call(&self, content: &str) -> Result<Post, errors::Save>;
+    async fn call(&self, author_id: &user::Id, content: &str) -> Result<Post, errors::Create>;
 }
 
 #[derive(Provider)]
 #[shaku(interface = Interface)]
 pub struct UseCase {
     #[shaku(provide)]
-    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    repository: Box<dyn post::RepositoryInterface + Send + Sync>,
+    #[shaku(provide)]
+    user_repository: Box<dyn user::RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    dispatcher: Arc<dyn DispatcherInterface>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
 }
 
 impl std::fmt::Debug for UseCase {
@@ -24,42 +62,83 @@ impl std::fmt::Debug for UseCase {
 #[async_trait]
 impl Interface for UseCase {
     #[tracing::instrument(name = "Create a post")]
-    async fn call(&self, content: &str) -> Result<Post, errors::Save> {
-        let post = Post::new(content);
+    async fn call(&self, author_id: &user::Id, content: &str) -> Result<Post, errors::Create> {
+        let content = PostContent::parse(content)?;
+        self.user_repository
+            .find(author_id)
+            .await
+            .map_err(|err| match err {
+                user::errors::Find::NotFound => errors::Create::AuthorNotFound,
+                user::errors::Find::Other(err) => errors::Create::Other(err),
+            })?;
+
+        let mut post = Post::new(*author_id, content, self.clock.now());
         self.repository.save(&post).await?;
+        self.dispatcher.dispatch(post.take_events()).await;
         Ok(post)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::super::super::post::{errors, Id, Post, RepositoryInterface};
-    use super::{Interface, UseCase};
-    use async_trait::async_trait;
+    use super::super::super::{
+        clock::test::FixedClock,
+        events::{test::FakeDispatcher, DomainEvent},
+        libs::id::Identifier,
+        post::test::FakeRepository,
+        user::{self, test::FakeRepository as FakeUserRepository, User},
+    };
+    use super::{errors::Create, Interface, UseCase};
+    use chrono::{TimeZone, Utc};
+    use std::sync::Arc;
 
-    struct FakeRepository;
-
-    #[async_trait]
-    impl RepositoryInterface for FakeRepository {
-        async fn find(&self, _id: &Id) -> Result<Post, errors::Find> {
-            Ok(Post::new("todo"))
-        }
+    #[tokio::test]
+    async fn it_creates_a_post() {
+        let now = Utc.ymd(2021, 2, 21).and_hms(10, 0, 0);
+        let author = User::new("Elliot".parse().unwrap(), now);
+        let author_id = *author.id();
+        let posts = FakeRepository::default();
+        let dispatcher = Arc::new(FakeDispatcher::default());
+        let use_case = UseCase {
+            repository: Box::new(posts.clone()),
+            user_repository: Box::new(FakeUserRepository::with(&[author])),
+            dispatcher: dispatcher.clone(),
+            clock: Arc::new(FixedClock(now)),
+        };
 
-        async fn save(&self, _post: &Post) -> Result<(), errors::Save> {
-            println!("Call the fake save...");
-            Ok(())
-        }
+        let result = use_case.call(&author_id, "My new post").await;
+        assert!(result.is_ok());
+        let post = result.unwrap();
+        assert_eq!(post.content(), "My new post");
+        assert_eq!(post.author_id(), &author_id);
+        assert_eq!(post.created_at(), &now);
+        assert_eq!(post.updated_at(), &now);
+        assert!(posts.get(post.id()).is_some());
+        assert_eq!(
+            *dispatcher.dispatched.lock().unwrap(),
+            vec![DomainEvent::PostCreated {
+                id: *post.id(),
+                author_id,
+                content: "My new post".into(),
+                created_at: now,
+            }]
+        );
     }
 
     #[tokio::test]
-    async fn it_creates_a_post() {
+    async fn it_fails_when_the_author_does_not_exist() {
+        let posts = FakeRepository::default();
+        let dispatcher = Arc::new(FakeDispatcher::default());
         let use_case = UseCase {
-            repository: Box::new(FakeRepository {}),
+            repository: Box::new(posts.clone()),
+            user_repository: Box::new(FakeUserRepository::default()),
+            dispatcher: dispatcher.clone(),
+            clock: Arc::new(FixedClock::default()),
         };
 
-        // Fake id call
-        let result = use_case.call("My new post").await;
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap().content(), "My new post");
+        let result = use_case.call(&user::Id::generate(), "My new post").await;
+        assert!(matches!(result, Err(Create::AuthorNotFound)));
+        assert_eq!(posts.count(), 0);
+        assert!(dispatcher.dispatched.lock().unwrap().is_empty());
     }
 }
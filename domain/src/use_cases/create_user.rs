@@ -1,11 +1,44 @@
 use async_trait::async_trait;
 use shaku::Provider;
+use std::sync::Arc;
 
-use super::super::user::{errors, RepositoryInterface, User};
+use super::super::{
+    clock::ClockInterface,
+    events::DispatcherInterface,
+    user::{self, Nickname, RepositoryInterface, User},
+};
+
+pub mod errors {
+    use super::user;
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Create {
+        #[error(transparent)]
+        InvalidNickname(#[from] user::nickname::errors::Invalid),
+
+        #[error("Nickname \"{nickname}\" already exists")]
+        NicknameExists { nickname: String },
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<user::errors::Save> for Create {
+        fn from(err: user::errors::Save) -> Self {
+            match err {
+                user::errors::Save::NicknameExists { nickname } => {
+                    Create::NicknameExists { nickname }
+                }
+                user::errors::Save::Other(err) => Create::Other(err),
+            }
+        }
+    }
+}
 
 #[async_trait]
 pub trait Interface: Send + Sync {
-    async fn call(&self, nickname: &str) -> Result<User, errors::Save>;
+    async fn call(&self, nickname: &str) -> Result<User, errors::Create>;
 }
 
 #[derive(Provider)]
@@ -13,6 +46,10 @@ pub trait Interface: Send + Sync {
 pub struct UseCase {
     #[shaku(provide)]
     repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    dispatcher: Arc<dyn DispatcherInterface>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
 }
 
 impl std::fmt::Debug for UseCase {
@@ -24,46 +61,67 @@ impl std::fmt::Debug for UseCase {
 #[async_trait]
 impl Interface for UseCase {
     #[tracing::instrument(name = "Create a user")]
-    async fn call(&self, nickname: &str) -> Result<User, errors::Save> {
-        let user = User::new(nickname);
+    async fn call(&self, nickname: &str) -> Result<User, errors::Create> {
+        let mut user = User::new(Nickname::parse(nickname)?, self.clock.now());
         self.repository.save(&user).await?;
+        self.dispatcher.dispatch(user.take_events()).await;
         Ok(user)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::super::super::user::{errors, Id, RepositoryInterface, User};
-    use super::{Interface, UseCase};
-    use async_trait::async_trait;
-
-    struct FakeRepository;
-
-    #[async_trait]
-    impl RepositoryInterface for FakeRepository {
-        async fn find(&self, _id: &Id) -> Result<User, errors::Find> {
-            Ok(User::new("todo"))
-        }
-
-        async fn find_by_nickname(&self, nickname: &str) -> Result<User, errors::Find> {
-            Ok(User::new(nickname)) // TODO
-        }
-
-        async fn save(&self, _user: &User) -> Result<(), errors::Save> {
-            println!("Call the fake save...");
-            Ok(())
-        }
-    }
+    use super::super::super::{
+        clock::test::FixedClock,
+        events::{test::FakeDispatcher, DomainEvent},
+        user::test::FakeRepository,
+    };
+    use super::{errors::Create, Interface, UseCase};
+    use chrono::{TimeZone, Utc};
+    use std::sync::Arc;
 
     #[tokio::test]
     async fn it_creates_a_user() {
+        let now = Utc.ymd(2021, 2, 21).and_hms(10, 0, 0);
+        let users = FakeRepository::default();
+        let dispatcher = Arc::new(FakeDispatcher::default());
         let use_case = UseCase {
-            repository: Box::new(FakeRepository {}),
+            repository: Box::new(users.clone()),
+            dispatcher: dispatcher.clone(),
+            clock: Arc::new(FixedClock(now)),
         };
 
-        // Fake id call
         let result = use_case.call("Elliot").await;
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().nickname(), "Elliot");
+        let user = result.unwrap();
+        assert_eq!(user.nickname(), "Elliot");
+        assert_eq!(user.created_at(), &now);
+        assert_eq!(user.updated_at(), &now);
+        assert!(user.events().is_empty());
+        assert!(users.get(user.id()).is_some());
+        assert_eq!(
+            *dispatcher.dispatched.lock().unwrap(),
+            vec![DomainEvent::UserCreated {
+                id: *user.id(),
+                nickname: "Elliot".into(),
+                created_at: now,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_an_invalid_nickname() {
+        let users = FakeRepository::default();
+        let dispatcher = Arc::new(FakeDispatcher::default());
+        let use_case = UseCase {
+            repository: Box::new(users.clone()),
+            dispatcher: dispatcher.clone(),
+            clock: Arc::new(FixedClock::default()),
+        };
+
+        let result = use_case.call(" ").await;
+        assert!(matches!(result, Err(Create::InvalidNickname(_))));
+        assert_eq!(users.count(), 0);
+        assert!(dispatcher.dispatched.lock().unwrap().is_empty());
     }
 }
@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    clock::ClockInterface,
+    events::DispatcherInterface,
+    post::{self, Post, PostContent},
+    unit_of_work::{self, UnitOfWorkInterface},
+    user::{self, Nickname, User},
+};
+
+pub mod errors {
+    use super::{post, unit_of_work, user};
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Create {
+        #[error(transparent)]
+        InvalidNickname(#[from] user::nickname::errors::Invalid),
+
+        #[error(transparent)]
+        InvalidContent(#[from] post::content::errors::Invalid),
+
+        #[error(transparent)]
+        User(#[from] user::errors::Save),
+
+        #[error(transparent)]
+        Post(#[from] post::errors::Save),
+
+        #[error(transparent)]
+        Begin(#[from] unit_of_work::errors::Begin),
+
+        #[error(transparent)]
+        Commit(#[from] unit_of_work::errors::Commit),
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    async fn call(&self, nickname: &str, content: &str) -> Result<(User, Post), errors::Create>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    unit_of_work: Box<dyn UnitOfWorkInterface + Send + Sync>,
+    #[shaku(inject)]
+    dispatcher: Arc<dyn DispatcherInterface>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CreateUserWithPostUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Create a user with a first post")]
+    async fn call(&self, nickname: &str, content: &str) -> Result<(User, Post), errors::Create> {
+        let nickname = Nickname::parse(nickname)?;
+        let content = PostContent::parse(content)?;
+        let now = self.clock.now();
+        let transaction = self.unit_of_work.begin().await?;
+
+        let mut user = User::new(nickname, now);
+        transaction.users().save(&user).await?;
+
+        let mut post = Post::new(*user.id(), content, now);
+        transaction.posts().save(&post).await?;
+
+        transaction.commit().await?;
+
+        let mut events = user.take_events();
+        events.append(&mut post.take_events());
+        self.dispatcher.dispatch(events).await;
+
+        Ok((user, post))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        clock::test::FixedClock,
+        events::test::FakeDispatcher,
+        post,
+        unit_of_work::{self, Transaction, UnitOfWorkInterface},
+        user::{self, User},
+    };
+    use super::{errors, Interface, UseCase};
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    /// Writes go straight to the shared repositories, the test only checks the commit happened.
+    #[derive(Clone, Default)]
+    struct FakeUnitOfWork {
+        users: user::test::FakeRepository,
+        posts: post::test::FakeRepository,
+        committed: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl Transaction for FakeUnitOfWork {
+        fn users(&self) -> &dyn user::RepositoryInterface {
+            &self.users
+        }
+
+        fn posts(&self) -> &dyn post::RepositoryInterface {
+            &self.posts
+        }
+
+        async fn commit(self: Box<Self>) -> Result<(), unit_of_work::errors::Commit> {
+            self.committed.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn rollback(self: Box<Self>) -> Result<(), unit_of_work::errors::Rollback> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl UnitOfWorkInterface for FakeUnitOfWork {
+        async fn begin(&self) -> Result<Box<dyn Transaction>, unit_of_work::errors::Begin> {
+            Ok(Box::new(self.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn it_creates_a_user_and_a_post_in_one_transaction() {
+        let unit_of_work = FakeUnitOfWork::default();
+        let dispatcher = Arc::new(FakeDispatcher::default());
+        let use_case = UseCase {
+            unit_of_work: Box::new(unit_of_work.clone()),
+            dispatcher: dispatcher.clone(),
+            clock: Arc::new(FixedClock::default()),
+        };
+
+        let result = use_case.call("Elliot", "Hello friend").await;
+        assert!(result.is_ok());
+        let (user, post) = result.unwrap();
+        assert_eq!(user.nickname(), "Elliot");
+        assert_eq!(post.content(), "Hello friend");
+
+        assert!(unit_of_work.users.get(user.id()).is_some());
+        assert!(unit_of_work.posts.get(post.id()).is_some());
+        assert!(unit_of_work.committed.load(Ordering::SeqCst));
+
+        let dispatched = dispatcher.dispatched.lock().unwrap();
+        let names: Vec<&str> = dispatched.iter().map(|event| event.name()).collect();
+        assert_eq!(names, vec!["UserCreated", "PostCreated"]);
+    }
+
+    #[tokio::test]
+    async fn it_does_not_commit_when_a_write_fails() {
+        let unit_of_work = FakeUnitOfWork {
+            users: user::test::FakeRepository::with(&[User::new(
+                "Elliot".parse().unwrap(),
+                Utc::now(),
+            )]),
+            ..FakeUnitOfWork::default()
+        };
+        let dispatcher = Arc::new(FakeDispatcher::default());
+        let use_case = UseCase {
+            unit_of_work: Box::new(unit_of_work.clone()),
+            dispatcher: dispatcher.clone(),
+            clock: Arc::new(FixedClock::default()),
+        };
+
+        let result = use_case.call("Elliot", "Hello friend").await;
+        assert!(matches!(
+            result,
+            Err(errors::Create::User(
+                user::errors::Save::NicknameExists { .. }
+            ))
+        ));
+
+        assert_eq!(unit_of_work.users.count(), 1);
+        assert_eq!(unit_of_work.posts.count(), 0);
+        assert!(!unit_of_work.committed.load(Ordering::SeqCst));
+        assert!(dispatcher.dispatched.lock().unwrap().is_empty());
+    }
+}
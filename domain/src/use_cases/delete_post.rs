@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    clock::ClockInterface,
+    policies::{self, Actor},
+    post::{self, Id, RepositoryInterface},
+};
+
+pub mod errors {
+    use super::{policies, post};
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Delete {
+        #[error("Post not found")]
+        NotFound,
+
+        #[error("Post was modified by someone else")]
+        Conflict,
+
+        #[error(transparent)]
+        Forbidden(#[from] policies::Forbidden),
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<post::errors::Find> for Delete {
+        fn from(err: post::errors::Find) -> Self {
+            match err {
+                post::errors::Find::NotFound => Delete::NotFound,
+                post::errors::Find::Other(err) => Delete::Other(err),
+            }
+        }
+    }
+
+    impl From<post::errors::Update> for Delete {
+        fn from(err: post::errors::Update) -> Self {
+            match err {
+                post::errors::Update::NotFound => Delete::NotFound,
+                post::errors::Update::Conflict => Delete::Conflict,
+                post::errors::Update::Other(err) => Delete::Other(err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    /// Soft delete the post, an admin can restore it.
+    async fn call(&self, actor: &Actor, id: &Id) -> Result<(), errors::Delete>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeletePostUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Delete a post")]
+    async fn call(&self, actor: &Actor, id: &Id) -> Result<(), errors::Delete> {
+        let mut post = self.repository.find(id).await?;
+        policies::can_delete_post(actor, &post)?;
+
+        post.delete(self.clock.now());
+        Ok(self.repository.update(&post).await?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        clock::test::FixedClock,
+        libs::id::Identifier,
+        policies::Actor,
+        post::{test::FakeRepository, Id, Post},
+        user::{self, Role},
+    };
+    use super::{errors::Delete, Interface, UseCase};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    fn use_case(posts: &FakeRepository) -> UseCase {
+        UseCase {
+            repository: Box::new(posts.clone()),
+            clock: Arc::new(FixedClock::default()),
+        }
+    }
+
+    /// A repository holding one post of `author_id`, and the id of that post.
+    fn post_of(author_id: &user::Id) -> (FakeRepository, Id) {
+        let post = Post::new(*author_id, "My post".parse().unwrap(), Utc::now());
+        let post_id = *post.id();
+        (FakeRepository::with(&[post]), post_id)
+    }
+
+    #[tokio::test]
+    async fn it_deletes_a_post() {
+        let author = Actor::new(user::Id::generate(), Role::Member);
+        let (posts, post_id) = post_of(author.id());
+
+        assert!(use_case(&posts).call(&author, &post_id).await.is_ok());
+        assert_eq!(
+            posts.get(&post_id).unwrap().deleted_at(),
+            Some(&FixedClock::default().0)
+        );
+    }
+
+    #[tokio::test]
+    async fn it_forbids_deleting_the_post_of_someone_else() {
+        let someone_else = Actor::new(user::Id::generate(), Role::Member);
+        let (posts, post_id) = post_of(&user::Id::generate());
+
+        let result = use_case(&posts).call(&someone_else, &post_id).await;
+        assert!(matches!(result, Err(Delete::Forbidden(_))));
+        assert!(!posts.get(&post_id).unwrap().is_deleted());
+    }
+
+    #[tokio::test]
+    async fn it_fails_when_the_post_does_not_exist() {
+        let author = Actor::new(user::Id::generate(), Role::Member);
+
+        let result = use_case(&FakeRepository::default())
+            .call(&author, &Id::generate())
+            .await;
+        assert!(matches!(result, Err(Delete::NotFound)));
+    }
+}
@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    clock::ClockInterface,
+    policies::{self, Actor},
+    user::{self, Id, RepositoryInterface},
+};
+
+pub mod errors {
+    use super::{policies, user};
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Delete {
+        #[error("User not found")]
+        NotFound,
+
+        #[error("User was modified by someone else")]
+        Conflict,
+
+        #[error(transparent)]
+        Forbidden(#[from] policies::Forbidden),
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<user::errors::Find> for Delete {
+        fn from(err: user::errors::Find) -> Self {
+            match err {
+                user::errors::Find::NotFound => Delete::NotFound,
+                user::errors::Find::Other(err) => Delete::Other(err),
+            }
+        }
+    }
+
+    impl From<user::errors::Update> for Delete {
+        fn from(err: user::errors::Update) -> Self {
+            match err {
+                user::errors::Update::NotFound => Delete::NotFound,
+                user::errors::Update::Conflict => Delete::Conflict,
+                err => Delete::Other(err.into()),
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    /// Soft delete the user, an admin can restore them.
+    async fn call(&self, actor: &Actor, id: &Id) -> Result<(), errors::Delete>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeleteUserUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Delete a user")]
+    async fn call(&self, actor: &Actor, id: &Id) -> Result<(), errors::Delete> {
+        policies::can_delete_user(actor, id)?;
+
+        let mut user = self.repository.find(id).await?;
+        user.delete(self.clock.now());
+        Ok(self.repository.update(&user).await?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        clock::test::FixedClock,
+        libs::id::Identifier,
+        policies::Actor,
+        user::{test::FakeRepository, Id, Role, User},
+    };
+    use super::{errors::Delete, Interface, UseCase};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    fn use_case(users: &FakeRepository) -> UseCase {
+        UseCase {
+            repository: Box::new(users.clone()),
+            clock: Arc::new(FixedClock::default()),
+        }
+    }
+
+    /// A repository holding Elliot, and the id of Elliot.
+    fn elliot() -> (FakeRepository, Id) {
+        let user = User::new("Elliot".parse().unwrap(), Utc::now());
+        let id = *user.id();
+        (FakeRepository::with(&[user]), id)
+    }
+
+    #[tokio::test]
+    async fn it_lets_an_admin_delete_any_user() {
+        let admin = Actor::new(Id::generate(), Role::Admin);
+        let (users, id) = elliot();
+
+        let result = use_case(&users).call(&admin, &id).await;
+        assert!(result.is_ok());
+        assert!(users.get(&id).unwrap().is_deleted());
+    }
+
+    #[tokio::test]
+    async fn it_lets_a_user_delete_their_own_account() {
+        let (users, id) = elliot();
+
+        let result = use_case(&users)
+            .call(&Actor::new(id, Role::Member), &id)
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(
+            users.get(&id).unwrap().deleted_at(),
+            Some(&FixedClock::default().0)
+        );
+    }
+
+    #[tokio::test]
+    async fn it_forbids_members_to_delete_someone_else() {
+        let member = Actor::new(Id::generate(), Role::Member);
+        let (users, id) = elliot();
+
+        let result = use_case(&users).call(&member, &id).await;
+        assert!(matches!(result, Err(Delete::Forbidden(_))));
+        assert!(!users.get(&id).unwrap().is_deleted());
+    }
+}
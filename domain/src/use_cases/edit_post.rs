@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    clock::ClockInterface,
+    policies::{self, Actor},
+    post::{self, Id, Post, PostContent, RepositoryInterface},
+};
+
+pub mod errors {
+    use super::{policies, post};
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Edit {
+        #[error("Post not found")]
+        NotFound,
+
+        #[error(transparent)]
+        InvalidContent(#[from] post::content::errors::Invalid),
+
+        #[error("Post was modified by someone else")]
+        Conflict,
+
+        #[error(transparent)]
+        Forbidden(#[from] policies::Forbidden),
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<post::errors::Find> for Edit {
+        fn from(err: post::errors::Find) -> Self {
+            match err {
+                post::errors::Find::NotFound => Edit::NotFound,
+                post::errors::Find::Other(err) => Edit::Other(err),
+            }
+        }
+    }
+
+    impl From<post::errors::Update> for Edit {
+        fn from(err: post::errors::Update) -> Self {
+            match err {
+                post::errors::Update::NotFound => Edit::NotFound,
+                post::errors::Update::Conflict => Edit::Conflict,
+                post::errors::Update::Other(err) => Edit::Other(err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    /// Fails with a conflict when `expected_version` is given and the post is at another version.
+    async fn call(
+        &self,
+        actor: &Actor,
+        id: &Id,
+        content: &str,
+        expected_version: Option<i32>,
+    ) -> Result<Post, errors::Edit>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EditPostUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Edit a post")]
+    async fn call(
+        &self,
+        actor: &Actor,
+        id: &Id,
+        content: &str,
+        expected_version: Option<i32>,
+    ) -> Result<Post, errors::Edit> {
+        let content = PostContent::parse(content)?;
+        let mut post = self.repository.find(id).await?;
+        policies::can_edit_post(actor, &post)?;
+        if expected_version.map_or(false, |version| version != post.version()) {
+            return Err(errors::Edit::Conflict);
+        }
+
+        post.edit(content, self.clock.now());
+        self.repository.update(&post).await?;
+        Ok(post)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        clock::test::FixedClock,
+        libs::id::Identifier,
+        policies::Actor,
+        post::{test::FakeRepository, Id, Post},
+        user::{self, Role},
+    };
+    use super::{errors::Edit, Interface, UseCase};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    fn use_case(posts: &FakeRepository) -> UseCase {
+        UseCase {
+            repository: Box::new(posts.clone()),
+            clock: Arc::new(FixedClock::default()),
+        }
+    }
+
+    /// A repository holding one post of `author_id`, and the id of that post.
+    fn post_of(author_id: &user::Id) -> (FakeRepository, Id) {
+        let post = Post::new(*author_id, "My post".parse().unwrap(), Utc::now());
+        let post_id = *post.id();
+        (FakeRepository::with(&[post]), post_id)
+    }
+
+    #[tokio::test]
+    async fn it_edits_a_post() {
+        let author = Actor::new(user::Id::generate(), Role::Member);
+        let (posts, post_id) = post_of(author.id());
+
+        let result = use_case(&posts)
+            .call(&author, &post_id, "My edited post", None)
+            .await;
+        assert!(result.is_ok());
+        let post = result.unwrap();
+        assert_eq!(post.content(), "My edited post");
+        assert_eq!(post.updated_at(), &FixedClock::default().0);
+        assert_eq!(post.version(), 2);
+        assert_eq!(posts.get(&post_id).unwrap().content(), "My edited post");
+    }
+
+    #[tokio::test]
+    async fn it_fails_when_the_post_changed_since_the_expected_version() {
+        let author = Actor::new(user::Id::generate(), Role::Member);
+        let (posts, post_id) = post_of(author.id());
+
+        let result = use_case(&posts)
+            .call(&author, &post_id, "My edited post", Some(3))
+            .await;
+        assert!(matches!(result, Err(Edit::Conflict)));
+    }
+
+    #[tokio::test]
+    async fn it_forbids_editing_the_post_of_someone_else() {
+        let admin = Actor::new(user::Id::generate(), Role::Admin);
+        let (posts, post_id) = post_of(&user::Id::generate());
+
+        let result = use_case(&posts)
+            .call(&admin, &post_id, "My edited post", None)
+            .await;
+        assert!(matches!(result, Err(Edit::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn it_fails_when_the_post_does_not_exist() {
+        let author = Actor::new(user::Id::generate(), Role::Member);
+
+        let result = use_case(&FakeRepository::default())
+            .call(&author, &Id::generate(), "My edited post", None)
+            .await;
+        assert!(matches!(result, Err(Edit::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_empty_content() {
+        let author = Actor::new(user::Id::generate(), Role::Member);
+        let (posts, post_id) = post_of(author.id());
+
+        let result = use_case(&posts).call(&author, &post_id, "  ", None).await;
+        assert!(matches!(result, Err(Edit::InvalidContent(_))));
+    }
+}
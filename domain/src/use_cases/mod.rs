@@ -1,2 +1,11 @@
 pub mod create_post;
 pub mod create_user;
+pub mod create_user_with_post;
+pub mod delete_post;
+pub mod delete_user;
+pub mod edit_post;
+pub mod rename_user;
+pub mod restore_post;
+pub mod restore_user;
+pub mod sign_in;
+pub mod sign_up;
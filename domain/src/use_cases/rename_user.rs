@@ -0,0 +1,193 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    clock::ClockInterface,
+    policies::{self, Actor},
+    user::{self, Id, Nickname, RepositoryInterface, User},
+};
+
+pub mod errors {
+    use super::{policies, user};
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Rename {
+        #[error("User not found")]
+        NotFound,
+
+        #[error(transparent)]
+        InvalidNickname(#[from] user::nickname::errors::Invalid),
+
+        #[error("Nickname \"{nickname}\" already exists")]
+        NicknameExists { nickname: String },
+
+        #[error("User was modified by someone else")]
+        Conflict,
+
+        #[error(transparent)]
+        Forbidden(#[from] policies::Forbidden),
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<user::errors::Find> for Rename {
+        fn from(err: user::errors::Find) -> Self {
+            match err {
+                user::errors::Find::NotFound => Rename::NotFound,
+                user::errors::Find::Other(err) => Rename::Other(err),
+            }
+        }
+    }
+
+    impl From<user::errors::Update> for Rename {
+        fn from(err: user::errors::Update) -> Self {
+            match err {
+                user::errors::Update::NotFound => Rename::NotFound,
+                user::errors::Update::Conflict => Rename::Conflict,
+                user::errors::Update::NicknameExists { nickname } => {
+                    Rename::NicknameExists { nickname }
+                }
+                user::errors::Update::Other(err) => Rename::Other(err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    /// Fails with a conflict when `expected_version` is given and the user is at another version.
+    async fn call(
+        &self,
+        actor: &Actor,
+        id: &Id,
+        nickname: &str,
+        expected_version: Option<i32>,
+    ) -> Result<User, errors::Rename>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenameUserUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Rename a user")]
+    async fn call(
+        &self,
+        actor: &Actor,
+        id: &Id,
+        nickname: &str,
+        expected_version: Option<i32>,
+    ) -> Result<User, errors::Rename> {
+        policies::can_rename_user(actor, id)?;
+        let nickname = Nickname::parse(nickname)?;
+
+        let mut user = self.repository.find(id).await?;
+        if expected_version.map_or(false, |version| version != user.version()) {
+            return Err(errors::Rename::Conflict);
+        }
+        user.rename(nickname, self.clock.now());
+        self.repository.update(&user).await?;
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        clock::test::FixedClock,
+        libs::id::Identifier,
+        policies::Actor,
+        user::{test::FakeRepository, Id, Role, User},
+    };
+    use super::{errors::Rename, Interface, UseCase};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    /// A use case over a repository holding Elliot and Darlene, and the id of Elliot.
+    fn use_case() -> (UseCase, Id) {
+        let elliot = User::new("Elliot".parse().unwrap(), Utc::now());
+        let darlene = User::new("Darlene".parse().unwrap(), Utc::now());
+        let id = *elliot.id();
+        let use_case = UseCase {
+            repository: Box::new(FakeRepository::with(&[elliot, darlene])),
+            clock: Arc::new(FixedClock::default()),
+        };
+        (use_case, id)
+    }
+
+    #[tokio::test]
+    async fn it_renames_a_user() {
+        let (use_case, id) = use_case();
+        let actor = Actor::new(id, Role::Member);
+
+        let result = use_case.call(&actor, &id, "Mr. Robot", None).await;
+        assert!(result.is_ok());
+        let user = result.unwrap();
+        assert_eq!(user.nickname(), "Mr. Robot");
+        assert_eq!(user.updated_at(), &FixedClock::default().0);
+        assert_eq!(user.version(), 2);
+    }
+
+    #[tokio::test]
+    async fn it_fails_when_the_user_changed_since_the_expected_version() {
+        let (use_case, id) = use_case();
+        let actor = Actor::new(id, Role::Member);
+
+        let result = use_case.call(&actor, &id, "Mr. Robot", Some(0)).await;
+        assert!(matches!(result, Err(Rename::Conflict)));
+    }
+
+    #[tokio::test]
+    async fn it_lets_an_admin_rename_anyone() {
+        let (use_case, id) = use_case();
+        let actor = Actor::new(Id::generate(), Role::Admin);
+
+        let result = use_case.call(&actor, &id, "Mr. Robot", None).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_forbids_renaming_someone_else() {
+        let (use_case, id) = use_case();
+        let actor = Actor::new(Id::generate(), Role::Member);
+
+        let result = use_case.call(&actor, &id, "Mr. Robot", None).await;
+        assert!(matches!(result, Err(Rename::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn it_rejects_an_invalid_nickname() {
+        let (use_case, id) = use_case();
+        let actor = Actor::new(id, Role::Member);
+
+        let result = use_case.call(&actor, &id, "root", None).await;
+        assert!(matches!(result, Err(Rename::InvalidNickname(_))));
+    }
+
+    #[tokio::test]
+    async fn it_fails_when_the_nickname_is_taken() {
+        let (use_case, id) = use_case();
+        let actor = Actor::new(id, Role::Member);
+
+        let result = use_case.call(&actor, &id, "Darlene", None).await;
+        assert!(matches!(
+            result,
+            Err(Rename::NicknameExists { nickname }) if nickname == "Darlene"
+        ));
+    }
+}
@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    clock::ClockInterface,
+    policies::{self, Actor},
+    post::{self, Id, Post, RepositoryInterface},
+};
+
+pub mod errors {
+    use super::{policies, post};
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Restore {
+        #[error("Post not found")]
+        NotFound,
+
+        #[error("Post was modified by someone else")]
+        Conflict,
+
+        #[error(transparent)]
+        Forbidden(#[from] policies::Forbidden),
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<post::errors::Find> for Restore {
+        fn from(err: post::errors::Find) -> Self {
+            match err {
+                post::errors::Find::NotFound => Restore::NotFound,
+                post::errors::Find::Other(err) => Restore::Other(err),
+            }
+        }
+    }
+
+    impl From<post::errors::Update> for Restore {
+        fn from(err: post::errors::Update) -> Self {
+            match err {
+                post::errors::Update::NotFound => Restore::NotFound,
+                post::errors::Update::Conflict => Restore::Conflict,
+                post::errors::Update::Other(err) => Restore::Other(err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    async fn call(&self, actor: &Actor, id: &Id) -> Result<Post, errors::Restore>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestorePostUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Restore a post")]
+    async fn call(&self, actor: &Actor, id: &Id) -> Result<Post, errors::Restore> {
+        policies::can_restore_post(actor)?;
+
+        let mut post = self.repository.find_including_deleted(id).await?;
+        if post.is_deleted() {
+            post.restore(self.clock.now());
+            self.repository.update(&post).await?;
+        }
+        Ok(post)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        clock::test::FixedClock,
+        libs::id::Identifier,
+        policies::Actor,
+        post::{test::FakeRepository, Id, Post},
+        user::{self, Role},
+    };
+    use super::{errors::Restore, Interface, UseCase};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    /// A use case over a repository holding one deleted post, and the id of that post.
+    fn use_case() -> (UseCase, Id) {
+        let mut post = Post::new(user::Id::generate(), "My post".parse().unwrap(), Utc::now());
+        post.delete(Utc::now());
+        let id = *post.id();
+        let use_case = UseCase {
+            repository: Box::new(FakeRepository::with(&[post])),
+            clock: Arc::new(FixedClock::default()),
+        };
+        (use_case, id)
+    }
+
+    #[tokio::test]
+    async fn it_lets_an_admin_restore_a_deleted_post() {
+        let (use_case, id) = use_case();
+        let admin = Actor::new(user::Id::generate(), Role::Admin);
+
+        let result = use_case.call(&admin, &id).await;
+        assert!(result.is_ok());
+        assert!(!result.unwrap().is_deleted());
+    }
+
+    #[tokio::test]
+    async fn it_forbids_members_to_restore_a_post() {
+        let (use_case, id) = use_case();
+        let member = Actor::new(user::Id::generate(), Role::Member);
+
+        let result = use_case.call(&member, &id).await;
+        assert!(matches!(result, Err(Restore::Forbidden(_))));
+    }
+}
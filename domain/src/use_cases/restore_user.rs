@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    clock::ClockInterface,
+    policies::{self, Actor},
+    user::{self, Id, RepositoryInterface, User},
+};
+
+pub mod errors {
+    use super::{policies, user};
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Restore {
+        #[error("User not found")]
+        NotFound,
+
+        #[error("User was modified by someone else")]
+        Conflict,
+
+        #[error(transparent)]
+        Forbidden(#[from] policies::Forbidden),
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<user::errors::Find> for Restore {
+        fn from(err: user::errors::Find) -> Self {
+            match err {
+                user::errors::Find::NotFound => Restore::NotFound,
+                user::errors::Find::Other(err) => Restore::Other(err),
+            }
+        }
+    }
+
+    impl From<user::errors::Update> for Restore {
+        fn from(err: user::errors::Update) -> Self {
+            match err {
+                user::errors::Update::NotFound => Restore::NotFound,
+                user::errors::Update::Conflict => Restore::Conflict,
+                err => Restore::Other(err.into()),
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    async fn call(&self, actor: &Actor, id: &Id) -> Result<User, errors::Restore>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RestoreUserUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Restore a user")]
+    async fn call(&self, actor: &Actor, id: &Id) -> Result<User, errors::Restore> {
+        policies::can_restore_user(actor)?;
+
+        let mut user = self.repository.find_including_deleted(id).await?;
+        if user.is_deleted() {
+            user.restore(self.clock.now());
+            self.repository.update(&user).await?;
+        }
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        clock::test::FixedClock,
+        libs::id::Identifier,
+        policies::Actor,
+        user::{test::FakeRepository, Id, Role, User},
+    };
+    use super::{errors::Restore, Interface, UseCase};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    /// A use case over a repository holding the deleted Elliot, and the id of Elliot.
+    fn use_case() -> (UseCase, Id) {
+        let mut elliot = User::new("Elliot".parse().unwrap(), Utc::now());
+        elliot.delete(Utc::now());
+        let id = *elliot.id();
+        let use_case = UseCase {
+            repository: Box::new(FakeRepository::with(&[elliot])),
+            clock: Arc::new(FixedClock::default()),
+        };
+        (use_case, id)
+    }
+
+    #[tokio::test]
+    async fn it_lets_an_admin_restore_a_deleted_user() {
+        let (use_case, id) = use_case();
+        let admin = Actor::new(Id::generate(), Role::Admin);
+
+        let result = use_case.call(&admin, &id).await;
+        assert!(result.is_ok());
+        let user = result.unwrap();
+        assert!(!user.is_deleted());
+        assert_eq!(user.version(), 3);
+    }
+
+    #[tokio::test]
+    async fn it_forbids_members_to_restore_a_user() {
+        let (use_case, id) = use_case();
+
+        let result = use_case.call(&Actor::new(id, Role::Member), &id).await;
+        assert!(matches!(result, Err(Restore::Forbidden(_))));
+    }
+}
@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    auth::{PasswordHasherInterface, Session, TokenServiceInterface},
+    user::{self, RepositoryInterface, User},
+};
+
+pub mod errors {
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum SignIn {
+        #[error("Invalid nickname or password")]
+        InvalidCredentials,
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    async fn call(&self, nickname: &str, password: &str) -> Result<Session, errors::SignIn>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    password_hasher: Arc<dyn PasswordHasherInterface>,
+    #[shaku(inject)]
+    token_service: Arc<dyn TokenServiceInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignInUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Sign in", skip(password))]
+    async fn call(&self, nickname: &str, password: &str) -> Result<Session, errors::SignIn> {
+        let user = match self.repository.find_by_nickname(nickname).await {
+            Ok(user) => Some(user),
+            Err(user::errors::Find::NotFound) => None,
+            Err(user::errors::Find::Other(err)) => return Err(err.into()),
+        };
+
+        // A password is verified even without a user or a hash to check it against, otherwise
+        // the response time would tell which nicknames exist.
+        let password_hash = user.as_ref().and_then(User::password_hash);
+        let has_password = password_hash.is_some();
+        let verified = self
+            .password_hasher
+            .verify(
+                password_hash.unwrap_or_else(|| self.password_hasher.dummy_hash()),
+                password,
+            )
+            .await?;
+        let user = match user {
+            Some(user) if has_password && verified => user,
+            _ => return Err(errors::SignIn::InvalidCredentials),
+        };
+
+        let token = self.token_service.issue(user.id())?;
+        Ok(Session { user, token })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        auth::{
+            test::{FakePasswordHasher, FakeTokenService},
+            TokenServiceInterface,
+        },
+        user::{test::FakeRepository, User},
+    };
+    use super::{errors::SignIn, Interface, UseCase};
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    /// A use case over a repository holding Elliot, whose password is "fsociety", and Tyrell,
+    /// who has no password.
+    fn use_case() -> UseCase {
+        let mut elliot = User::new("Elliot".parse().unwrap(), Utc::now());
+        elliot.set_password_hash("hashed:fsociety".into());
+        let tyrell = User::new("Tyrell".parse().unwrap(), Utc::now());
+
+        UseCase {
+            repository: Box::new(FakeRepository::with(&[elliot, tyrell])),
+            password_hasher: Arc::new(FakePasswordHasher),
+            token_service: Arc::new(FakeTokenService),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_signs_in_with_valid_credentials() {
+        let result = use_case().call("Elliot", "fsociety").await;
+        assert!(result.is_ok());
+        let session = result.unwrap();
+        assert_eq!(session.user.nickname(), "Elliot");
+        assert_eq!(
+            FakeTokenService.verify(&session.token).unwrap(),
+            *session.user.id()
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_invalid_credentials() {
+        for (nickname, password) in &[
+            ("Elliot", "wrong password"),
+            ("Darlene", "fsociety"),
+            ("Tyrell", "fsociety"),
+        ] {
+            let result = use_case().call(nickname, password).await;
+            assert!(matches!(result, Err(SignIn::InvalidCredentials)));
+        }
+    }
+}
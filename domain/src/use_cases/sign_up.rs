@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use shaku::Provider;
+use std::sync::Arc;
+
+use super::super::{
+    auth::{PasswordHasherInterface, Session, TokenServiceInterface, MIN_PASSWORD_LENGTH},
+    clock::ClockInterface,
+    events::DispatcherInterface,
+    user::{self, Nickname, RepositoryInterface, User},
+};
+
+pub mod errors {
+    use super::user;
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum SignUp {
+        #[error(transparent)]
+        InvalidNickname(#[from] user::nickname::errors::Invalid),
+
+        #[error("Password must be at least {min_length} characters long")]
+        PasswordTooShort { min_length: usize },
+
+        #[error("Nickname \"{nickname}\" already exists")]
+        NicknameExists { nickname: String },
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
+    impl From<user::errors::Save> for SignUp {
+        fn from(err: user::errors::Save) -> Self {
+            match err {
+                user::errors::Save::NicknameExists { nickname } => {
+                    SignUp::NicknameExists { nickname }
+                }
+                user::errors::Save::Other(err) => SignUp::Other(err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+pub trait Interface: Send + Sync {
+    async fn call(&self, nickname: &str, password: &str) -> Result<Session, errors::SignUp>;
+}
+
+#[derive(Provider)]
+#[shaku(interface = Interface)]
+pub struct UseCase {
+    #[shaku(provide)]
+    repository: Box<dyn RepositoryInterface + Send + Sync>,
+    #[shaku(inject)]
+    password_hasher: Arc<dyn PasswordHasherInterface>,
+    #[shaku(inject)]
+    token_service: Arc<dyn TokenServiceInterface>,
+    #[shaku(inject)]
+    dispatcher: Arc<dyn DispatcherInterface>,
+    #[shaku(inject)]
+    clock: Arc<dyn ClockInterface>,
+}
+
+impl std::fmt::Debug for UseCase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignUpUseCase").finish()
+    }
+}
+
+#[async_trait]
+impl Interface for UseCase {
+    #[tracing::instrument(name = "Sign up", skip(password))]
+    async fn call(&self, nickname: &str, password: &str) -> Result<Session, errors::SignUp> {
+        let nickname = Nickname::parse(nickname)?;
+        if password.chars().count() < MIN_PASSWORD_LENGTH {
+            return Err(errors::SignUp::PasswordTooShort {
+                min_length: MIN_PASSWORD_LENGTH,
+            });
+        }
+
+        let mut user = User::new(nickname, self.clock.now());
+        user.set_password_hash(self.password_hasher.hash(password).await?);
+        self.repository.save(&user).await?;
+        self.dispatcher.dispatch(user.take_events()).await;
+
+        let token = self.token_service.issue(user.id())?;
+        Ok(Session { user, token })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::{
+        auth::{
+            test::{FakePasswordHasher, FakeTokenService},
+            TokenServiceInterface,
+        },
+        clock::test::FixedClock,
+        events::test::FakeDispatcher,
+        user::test::FakeRepository,
+    };
+    use super::{errors::SignUp, Interface, UseCase};
+    use std::sync::Arc;
+
+    fn use_case(users: &FakeRepository) -> UseCase {
+        UseCase {
+            repository: Box::new(users.clone()),
+            password_hasher: Arc::new(FakePasswordHasher),
+            token_service: Arc::new(FakeTokenService),
+            dispatcher: Arc::new(FakeDispatcher::default()),
+            clock: Arc::new(FixedClock::default()),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_signs_up_a_user_with_a_hashed_password() {
+        let users = FakeRepository::default();
+
+        let result = use_case(&users).call("Elliot", "fsociety").await;
+        assert!(result.is_ok());
+        let session = result.unwrap();
+        assert_eq!(session.user.nickname(), "Elliot");
+        assert_eq!(
+            FakeTokenService.verify(&session.token).unwrap(),
+            *session.user.id()
+        );
+        assert_eq!(
+            users.get(session.user.id()).unwrap().password_hash(),
+            Some("hashed:fsociety")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_rejects_a_short_password() {
+        let users = FakeRepository::default();
+
+        let result = use_case(&users).call("Elliot", "fsoc").await;
+        assert!(matches!(result, Err(SignUp::PasswordTooShort { .. })));
+        assert_eq!(users.count(), 0);
+    }
+}
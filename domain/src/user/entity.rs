@@ -1,25 +1,120 @@
+use super::nickname::Nickname;
+use crate::events::DomainEvent;
 use crate::libs::id::{uuid, Display, FromStr, Identifier};
+use chrono::{DateTime, Utc};
 
-#[derive(Identifier, Eq, PartialEq, Display, FromStr, Debug)]
+#[derive(Identifier, Clone, Copy, Eq, PartialEq, Display, FromStr, Debug)]
 #[identifier(with = "uuid")]
 pub struct Id(u128);
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    Member,
+    Admin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Member => "member",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl std::str::FromStr for Role {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "member" => Ok(Role::Member),
+            "admin" => Ok(Role::Admin),
+            _ => Err(format!("Unknown role \"{}\"", value)),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq)]
 pub struct User {
     id: Id,
-    nickname: String,
+    nickname: Nickname,
+    password_hash: Option<String>,
+    role: Role,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
+    loaded_version: i32,
+    deleted_at: Option<DateTime<Utc>>,
+    events: Vec<DomainEvent>,
+}
+
+/// A user as kept by a repository, rebuilt with `User::build_from_repository`.
+pub struct StoredUser {
+    pub id: Id,
+    pub nickname: String,
+    pub password_hash: Option<String>,
+    pub role: Role,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i32,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// The password hash is redacted, users end up in the traces.
+impl std::fmt::Debug for User {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("nickname", &self.nickname)
+            .field(
+                "password_hash",
+                &self.password_hash.as_ref().map(|_| "<redacted>"),
+            )
+            .field("role", &self.role)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .field("version", &self.version)
+            .field("loaded_version", &self.loaded_version)
+            .field("deleted_at", &self.deleted_at)
+            .field("events", &self.events)
+            .finish()
+    }
 }
 
 impl User {
-    pub fn new(nickname: &str) -> User {
+    pub fn new(nickname: Nickname, now: DateTime<Utc>) -> User {
+        let id = Id::generate();
         User {
-            id: Id::generate(),
-            nickname: nickname.to_owned(),
+            id,
+            events: vec![DomainEvent::UserCreated {
+                id,
+                nickname: nickname.to_string(),
+                created_at: now,
+            }],
+            nickname,
+            password_hash: None,
+            role: Role::Member,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            loaded_version: 1,
+            deleted_at: None,
         }
     }
 
-    pub fn build_from_repository(id: Id, nickname: String) -> User {
-        User { id, nickname }
+    pub fn build_from_repository(stored: StoredUser) -> User {
+        User {
+            id: stored.id,
+            nickname: Nickname::build_from_repository(stored.nickname),
+            password_hash: stored.password_hash,
+            role: stored.role,
+            created_at: stored.created_at,
+            updated_at: stored.updated_at,
+            version: stored.version,
+            loaded_version: stored.version,
+            deleted_at: stored.deleted_at,
+            events: vec![],
+        }
     }
 
     pub fn id(&self) -> &Id {
@@ -27,6 +122,75 @@ impl User {
     }
 
     pub fn nickname(&self) -> &str {
-        &self.nickname
+        self.nickname.as_str()
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    /// Incremented when the user changes, so that concurrent writers can be detected.
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// Version the user had when loaded, a repository only overwrites a user still at this version.
+    pub fn loaded_version(&self) -> i32 {
+        self.loaded_version
+    }
+
+    /// Soft deleted users are hidden by the repositories but can be restored.
+    pub fn deleted_at(&self) -> Option<&DateTime<Utc>> {
+        self.deleted_at.as_ref()
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    pub fn delete(&mut self, now: DateTime<Utc>) {
+        self.deleted_at = Some(now);
+        self.touch(now);
+    }
+
+    pub fn restore(&mut self, now: DateTime<Utc>) {
+        self.deleted_at = None;
+        self.touch(now);
+    }
+
+    fn touch(&mut self, now: DateTime<Utc>) {
+        self.updated_at = now;
+        self.version = self.loaded_version + 1;
+    }
+
+    /// Users created without a password cannot sign in.
+    pub fn password_hash(&self) -> Option<&str> {
+        self.password_hash.as_deref()
+    }
+
+    pub fn set_password_hash(&mut self, password_hash: String) {
+        self.password_hash = Some(password_hash);
+    }
+
+    pub fn rename(&mut self, nickname: Nickname, now: DateTime<Utc>) {
+        self.nickname = nickname;
+        self.touch(now);
+    }
+
+    /// Events recorded since the user was loaded or created, not yet published.
+    pub fn events(&self) -> &[DomainEvent] {
+        &self.events
+    }
+
+    pub fn take_events(&mut self) -> Vec<DomainEvent> {
+        std::mem::take(&mut self.events)
     }
 }
@@ -1,7 +1,132 @@
 mod entity;
+pub mod nickname;
 mod repository;
 mod service;
 
 pub use entity::*;
+pub use nickname::Nickname;
 pub use repository::*;
 pub use service::*;
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::{errors, nickname, Id, RepositoryInterface, StoredUser, User};
+    use crate::libs::pagination::Page;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    /// Repository keeping the users in memory for the use case tests. Like the real ones it hides
+    /// the soft deleted users, keeps the nicknames unique and detects concurrent updates. Its
+    /// clones share the users, so that a test can look at what a use case wrote.
+    #[derive(Clone, Default)]
+    pub struct FakeRepository {
+        users: Arc<Mutex<Vec<User>>>,
+    }
+
+    /// Copy a user as a repository would load it, without its pending events.
+    fn stored(user: &User) -> User {
+        User::build_from_repository(StoredUser {
+            id: *user.id(),
+            nickname: user.nickname().into(),
+            password_hash: user.password_hash().map(Into::into),
+            role: user.role(),
+            created_at: *user.created_at(),
+            updated_at: *user.updated_at(),
+            version: user.version(),
+            deleted_at: user.deleted_at().copied(),
+        })
+    }
+
+    impl FakeRepository {
+        pub fn with(users: &[User]) -> FakeRepository {
+            FakeRepository {
+                users: Arc::new(Mutex::new(users.iter().map(stored).collect())),
+            }
+        }
+
+        /// The user as it is stored, soft deleted or not.
+        pub fn get(&self, id: &Id) -> Option<User> {
+            let users = self.users.lock().unwrap();
+            users.iter().find(|user| user.id() == id).map(stored)
+        }
+
+        pub fn count(&self) -> usize {
+            self.users.lock().unwrap().len()
+        }
+
+        fn find_where(&self, predicate: impl Fn(&User) -> bool) -> Result<User, errors::Find> {
+            let users = self.users.lock().unwrap();
+            users
+                .iter()
+                .find(|user| predicate(user))
+                .map(stored)
+                .ok_or(errors::Find::NotFound)
+        }
+
+        fn nickname_taken(users: &[User], user: &User) -> bool {
+            let key = nickname::key(user.nickname());
+            users
+                .iter()
+                .any(|other| other.id() != user.id() && nickname::key(other.nickname()) == key)
+        }
+    }
+
+    #[async_trait]
+    impl RepositoryInterface for FakeRepository {
+        async fn find(&self, id: &Id) -> Result<User, errors::Find> {
+            self.find_where(|user| user.id() == id && !user.is_deleted())
+        }
+
+        async fn find_including_deleted(&self, id: &Id) -> Result<User, errors::Find> {
+            self.find_where(|user| user.id() == id)
+        }
+
+        async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<User>, errors::List> {
+            let users = self.users.lock().unwrap();
+            let start = after
+                .and_then(|after| users.iter().position(|user| user.id() == after))
+                .map_or(0, |position| position + 1);
+            let items = users[start..]
+                .iter()
+                .filter(|user| !user.is_deleted())
+                .take(limit + 1)
+                .map(stored)
+                .collect();
+            Ok(Page::from_items(items, limit))
+        }
+
+        async fn find_by_nickname(&self, nickname: &str) -> Result<User, errors::Find> {
+            let key = nickname::key(nickname);
+            self.find_where(|user| nickname::key(user.nickname()) == key && !user.is_deleted())
+        }
+
+        async fn save(&self, user: &User) -> Result<(), errors::Save> {
+            let mut users = self.users.lock().unwrap();
+            if FakeRepository::nickname_taken(&users, user) {
+                return Err(errors::Save::NicknameExists {
+                    nickname: user.nickname().into(),
+                });
+            }
+            users.push(stored(user));
+            Ok(())
+        }
+
+        async fn update(&self, user: &User) -> Result<(), errors::Update> {
+            let mut users = self.users.lock().unwrap();
+            let position = users
+                .iter()
+                .position(|other| other.id() == user.id())
+                .ok_or(errors::Update::NotFound)?;
+            if users[position].version() != user.loaded_version() {
+                return Err(errors::Update::Conflict);
+            }
+            if FakeRepository::nickname_taken(&users, user) {
+                return Err(errors::Update::NicknameExists {
+                    nickname: user.nickname().into(),
+                });
+            }
+            users[position] = stored(user);
+            Ok(())
+        }
+    }
+}
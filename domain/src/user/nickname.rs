@@ -0,0 +1,172 @@
+use unicode_normalization::UnicodeNormalization;
+
+pub const MIN_LENGTH: usize = 2;
+pub const MAX_LENGTH: usize = 32;
+
+/// Nicknames that would let a user pass for the staff or the system.
+const RESERVED: &[&str] = &[
+    "admin",
+    "administrator",
+    "anonymous",
+    "me",
+    "moderator",
+    "null",
+    "root",
+    "system",
+];
+
+/// Key under which a nickname is unique and looked up: two nicknames differing
+/// only by case or Unicode representation share the same key.
+pub fn key(nickname: &str) -> String {
+    nickname
+        .nfkc()
+        .collect::<String>()
+        .trim()
+        .to_lowercase()
+        .nfkc()
+        .collect()
+}
+
+pub mod errors {
+    use thiserror::Error;
+
+    #[derive(Error, Debug, Eq, PartialEq)]
+    pub enum Invalid {
+        #[error("Nickname must be at least {min_length} characters long")]
+        TooShort { min_length: usize },
+
+        #[error("Nickname must be at most {max_length} characters long")]
+        TooLong { max_length: usize },
+
+        #[error("Nickname cannot contain \"{character}\"")]
+        ForbiddenCharacter { character: char },
+
+        #[error("Nickname \"{nickname}\" is reserved")]
+        Reserved { nickname: String },
+    }
+}
+
+/// A validated nickname: NFKC normalized, trimmed, made of letters, digits,
+/// spaces, `_`, `-` and `.`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Nickname(String);
+
+impl Nickname {
+    pub fn parse(value: &str) -> Result<Nickname, errors::Invalid> {
+        let nickname: String = value.nfkc().collect::<String>().trim().to_owned();
+
+        let length = nickname.chars().count();
+        if length < MIN_LENGTH {
+            return Err(errors::Invalid::TooShort {
+                min_length: MIN_LENGTH,
+            });
+        }
+        if length > MAX_LENGTH {
+            return Err(errors::Invalid::TooLong {
+                max_length: MAX_LENGTH,
+            });
+        }
+
+        if let Some(character) = nickname
+            .chars()
+            .find(|c| !(c.is_alphanumeric() || matches!(c, ' ' | '_' | '-' | '.')))
+        {
+            return Err(errors::Invalid::ForbiddenCharacter { character });
+        }
+
+        if RESERVED.contains(&nickname.to_lowercase().as_str()) {
+            return Err(errors::Invalid::Reserved { nickname });
+        }
+
+        Ok(Nickname(nickname))
+    }
+
+    /// Nicknames loaded from the repository were validated when they were saved.
+    pub(super) fn build_from_repository(nickname: String) -> Nickname {
+        Nickname(nickname)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn key(&self) -> String {
+        key(&self.0)
+    }
+}
+
+impl std::str::FromStr for Nickname {
+    type Err = errors::Invalid;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Nickname::parse(value)
+    }
+}
+
+impl std::fmt::Display for Nickname {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{errors::Invalid, key, Nickname, MAX_LENGTH, MIN_LENGTH};
+
+    #[test]
+    fn it_accepts_a_nickname() {
+        assert_eq!(Nickname::parse("Mr. Robot").unwrap().as_str(), "Mr. Robot");
+        assert_eq!(Nickname::parse("élliot_99").unwrap().as_str(), "élliot_99");
+    }
+
+    #[test]
+    fn it_trims_and_normalizes() {
+        // "e" followed by a combining acute accent, and full-width letters.
+        let nickname = Nickname::parse("  E\u{301}lliot ").unwrap();
+        assert_eq!(nickname.as_str(), "\u{c9}lliot");
+        assert_eq!(Nickname::parse("Ｅｌｌｉｏｔ").unwrap().as_str(), "Elliot");
+    }
+
+    #[test]
+    fn it_gives_the_same_key_regardless_of_case_and_representation() {
+        let nickname = Nickname::parse("Élliot").unwrap();
+        assert_eq!(nickname.key(), "élliot");
+        assert_eq!(key("ÉLLIOT"), nickname.key());
+        assert_eq!(key(" e\u{301}lliot"), nickname.key());
+        assert_eq!(key("Ｅｌｌｉｏｔ"), key("elliot"));
+    }
+
+    #[test]
+    fn it_rejects_a_nickname_too_short_or_too_long() {
+        assert_eq!(
+            Nickname::parse("   "),
+            Err(Invalid::TooShort {
+                min_length: MIN_LENGTH
+            })
+        );
+        assert_eq!(
+            Nickname::parse(&"a".repeat(MAX_LENGTH + 1)),
+            Err(Invalid::TooLong {
+                max_length: MAX_LENGTH
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_forbidden_characters() {
+        assert_eq!(
+            Nickname::parse("Elliot<script>"),
+            Err(Invalid::ForbiddenCharacter { character: '<' })
+        );
+    }
+
+    #[test]
+    fn it_rejects_reserved_nicknames() {
+        assert_eq!(
+            Nickname::parse("Admin"),
+            Err(Invalid::Reserved {
+                nickname: "Admin".into()
+            })
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use super::entity::{Id, User};
+use crate::libs::pagination::Page;
 use async_trait::async_trait;
 
 pub mod errors {
@@ -13,6 +14,12 @@ pub mod errors {
         Other(#[from] anyhow::Error),
     }
 
+    #[derive(Error, Debug)]
+    pub enum List {
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+
     #[derive(Error, Debug)]
     pub enum Save {
         #[error("Nickname \"{nickname}\" already exists")]
@@ -21,11 +28,30 @@ pub mod errors {
         #[error(transparent)]
         Other(#[from] anyhow::Error),
     }
+
+    #[derive(Error, Debug)]
+    pub enum Update {
+        #[error("User not found")]
+        NotFound,
+
+        #[error("User was modified by someone else")]
+        Conflict,
+
+        #[error("Nickname \"{nickname}\" already exists")]
+        NicknameExists { nickname: String },
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
 }
 
 #[async_trait]
 pub trait RepositoryInterface: Send + Sync {
+    /// Soft deleted users are not found, use `find_including_deleted` to see them.
     async fn find(&self, id: &Id) -> Result<User, errors::Find>;
+    async fn find_including_deleted(&self, id: &Id) -> Result<User, errors::Find>;
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<User>, errors::List>;
     async fn find_by_nickname(&self, nickname: &str) -> Result<User, errors::Find>;
     async fn save(&self, user: &User) -> Result<(), errors::Save>;
+    async fn update(&self, user: &User) -> Result<(), errors::Update>;
 }
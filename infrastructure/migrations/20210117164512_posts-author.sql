@@ -0,0 +1,13 @@
+-- The posts written before they had an author are attributed to a placeholder user, only created
+-- when there are such posts. Its nickname is rejected by `Nickname::parse` so that no one can take
+-- it, and it has no password so that no one can sign in as it.
+INSERT INTO users (id, nickname)
+SELECT '00000000-0000-4000-8000-000000000000', '[legacy]'
+WHERE EXISTS (SELECT 1 FROM posts);
+
+ALTER TABLE posts
+    ADD COLUMN author_id UUID CONSTRAINT posts_author_id_fkey REFERENCES users (id);
+UPDATE posts SET author_id = '00000000-0000-4000-8000-000000000000' WHERE author_id IS NULL;
+ALTER TABLE posts ALTER COLUMN author_id SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS posts_author_id_index ON posts (author_id);
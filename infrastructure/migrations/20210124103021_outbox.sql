@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS outbox
+(
+    id              BIGSERIAL              PRIMARY KEY,
+    event_type      CHARACTER VARYING(255) NOT NULL,
+    payload         JSONB                  NOT NULL,
+    created_at      TIMESTAMPTZ            NOT NULL DEFAULT NOW(),
+    attempts        INTEGER                NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ            NOT NULL DEFAULT NOW(),
+    last_error      TEXT,
+    published_at    TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS outbox_pending_index ON outbox (next_attempt_at) WHERE published_at IS NULL;
@@ -0,0 +1 @@
+ALTER TABLE users ADD COLUMN role CHARACTER VARYING(32) NOT NULL DEFAULT 'member';
@@ -0,0 +1,19 @@
+-- Nicknames are unique regardless of case and Unicode representation, the key is computed by the
+-- domain (`user::nickname::key`) and the backfill mirrors it: NFKC, trim the Unicode white space
+-- left after NFKC, lowercase, NFKC again. `normalize` needs PostgreSQL 13 and a UTF8 database,
+-- and the lowercasing uses the ICU root collation (PostgreSQL built with ICU) so that it folds
+-- every letter like Rust does, not only the ASCII ones as under the `C` locale.
+-- The migration fails if two existing rows collide, they need to be renamed first.
+ALTER TABLE users ADD COLUMN nickname_key CHARACTER VARYING(255);
+UPDATE users
+SET nickname_key = normalize(
+    lower(
+        btrim(normalize(nickname, NFKC), E' \t\n\u000B\f\r\u0085\u1680\u2028\u2029')
+            COLLATE "und-x-icu"
+    ),
+    NFKC
+);
+ALTER TABLE users
+    ALTER COLUMN nickname_key SET NOT NULL,
+    DROP CONSTRAINT users_nickname_unique,
+    ADD CONSTRAINT users_nickname_unique UNIQUE (nickname_key);
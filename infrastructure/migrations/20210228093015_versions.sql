@@ -0,0 +1,3 @@
+-- Incremented on every update, writers only overwrite the version they loaded.
+ALTER TABLE users ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE posts ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,3 @@
+-- Rows are never deleted, soft deleted ones are hidden from the listings and lookups.
+ALTER TABLE users ADD COLUMN deleted_at TIMESTAMPTZ;
+ALTER TABLE posts ADD COLUMN deleted_at TIMESTAMPTZ;
@@ -0,0 +1,42 @@
+-- Same schema as the Postgres migrations up to the soft delete: ids are hyphenated UUIDs stored as
+-- text, which sort like the UUIDs themselves, and payloads are JSON stored as text.
+CREATE TABLE IF NOT EXISTS users
+(
+    id            TEXT     PRIMARY KEY,
+    nickname      TEXT     NOT NULL,
+    nickname_key  TEXT     NOT NULL CONSTRAINT users_nickname_unique UNIQUE,
+    password_hash TEXT,
+    role          TEXT     NOT NULL DEFAULT 'member',
+    created_at    DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at    DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    version       INTEGER  NOT NULL DEFAULT 1,
+    deleted_at    DATETIME
+);
+
+CREATE TABLE IF NOT EXISTS posts
+(
+    id         TEXT     PRIMARY KEY,
+    author_id  TEXT     NOT NULL CONSTRAINT posts_author_id_fkey REFERENCES users (id),
+    content    TEXT     NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    version    INTEGER  NOT NULL DEFAULT 1,
+    deleted_at DATETIME
+);
+
+CREATE INDEX IF NOT EXISTS posts_author_id_index ON posts (author_id);
+CREATE INDEX IF NOT EXISTS posts_created_at_index ON posts (created_at);
+
+CREATE TABLE IF NOT EXISTS outbox
+(
+    id              INTEGER  PRIMARY KEY AUTOINCREMENT,
+    event_type      TEXT     NOT NULL,
+    payload         TEXT     NOT NULL,
+    created_at      DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    attempts        INTEGER  NOT NULL DEFAULT 0,
+    next_attempt_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_error      TEXT,
+    published_at    DATETIME
+);
+
+CREATE INDEX IF NOT EXISTS outbox_pending_index ON outbox (next_attempt_at) WHERE published_at IS NULL;
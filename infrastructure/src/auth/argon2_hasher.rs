@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use domain::auth::PasswordHasherInterface;
+use rand::RngCore;
+use shaku::Component;
+use tokio::task;
+
+/// Hash of a random password thrown away, with the parameters of `argon2::Config::default()`.
+const DUMMY_HASH: &str =
+    "$argon2i$v=19$m=4096,t=3,p=1$8wXN/7GiInSLTZ9bs4FgvA$gUJKPjAUulU9eju86J21l3NoYnLm25x1FLHu+ZVpZQ0";
+
+#[derive(Component)]
+#[shaku(interface = PasswordHasherInterface)]
+pub struct Argon2PasswordHasher {}
+
+/// Hashing and verifying run on the blocking pool, they take long enough to hold up the requests
+/// sharing the worker otherwise.
+#[async_trait]
+impl PasswordHasherInterface for Argon2PasswordHasher {
+    async fn hash(&self, password: &str) -> Result<String, anyhow::Error> {
+        let password = password.to_owned();
+        let hash = task::spawn_blocking(move || {
+            let mut salt = [0u8; 16];
+            rand::thread_rng().fill_bytes(&mut salt);
+            argon2::hash_encoded(password.as_bytes(), &salt, &argon2::Config::default())
+        })
+        .await??;
+        Ok(hash)
+    }
+
+    async fn verify(&self, password_hash: &str, password: &str) -> Result<bool, anyhow::Error> {
+        let (password_hash, password) = (password_hash.to_owned(), password.to_owned());
+        let verified = task::spawn_blocking(move || {
+            argon2::verify_encoded(&password_hash, password.as_bytes())
+        })
+        .await??;
+        Ok(verified)
+    }
+
+    fn dummy_hash(&self) -> &str {
+        DUMMY_HASH
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Argon2PasswordHasher;
+    use domain::auth::PasswordHasherInterface;
+
+    #[tokio::test]
+    async fn it_verifies_the_hashed_password() {
+        let hasher = Argon2PasswordHasher {};
+
+        let hash = hasher.hash("fsociety").await.unwrap();
+        assert_ne!(hash, "fsociety");
+        assert!(hasher.verify(&hash, "fsociety").await.unwrap());
+        assert!(!hasher.verify(&hash, "evilcorp").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn it_verifies_against_the_dummy_hash() {
+        let hasher = Argon2PasswordHasher {};
+
+        assert!(!hasher
+            .verify(hasher.dummy_hash(), "fsociety")
+            .await
+            .unwrap());
+    }
+}
@@ -0,0 +1,95 @@
+use domain::{
+    auth::{errors, TokenServiceInterface},
+    user::Id,
+};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use shaku::Component;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Settings of the signed session tokens, see `config::AuthConfig::token_config`.
+#[derive(Clone)]
+pub struct TokenConfig {
+    pub secret: String,
+    pub ttl: Duration,
+}
+
+impl std::fmt::Debug for TokenConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenConfig")
+            .field("secret", &"<redacted>")
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    exp: u64,
+}
+
+/// Issue and verify HS256 signed JSON web tokens.
+#[derive(Component)]
+#[shaku(interface = TokenServiceInterface)]
+pub struct JwtTokenService {
+    #[shaku(no_default)]
+    secret: String,
+    #[shaku(no_default)]
+    ttl: Duration,
+}
+
+impl TokenServiceInterface for JwtTokenService {
+    fn issue(&self, user_id: &Id) -> Result<String, anyhow::Error> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let claims = Claims {
+            sub: user_id.to_string(),
+            exp: (now + self.ttl).as_secs(),
+        };
+
+        Ok(encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(self.secret.as_bytes()),
+        )?)
+    }
+
+    fn verify(&self, token: &str) -> Result<Id, errors::Token> {
+        let data = decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(self.secret.as_bytes()),
+            &Validation::default(),
+        )
+        .map_err(|_| errors::Token::Invalid)?;
+
+        data.claims.sub.parse().map_err(|_| errors::Token::Invalid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::JwtTokenService;
+    use domain::{auth::TokenServiceInterface, libs::id::Identifier, user::Id};
+    use std::time::Duration;
+
+    fn token_service(secret: &str) -> JwtTokenService {
+        JwtTokenService {
+            secret: secret.into(),
+            ttl: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn it_verifies_an_issued_token() {
+        let user_id = Id::generate();
+
+        let token = token_service("secret").issue(&user_id).unwrap();
+        assert_eq!(token_service("secret").verify(&token).unwrap(), user_id);
+    }
+
+    #[test]
+    fn it_rejects_a_token_signed_with_another_secret() {
+        let token = token_service("secret").issue(&Id::generate()).unwrap();
+        assert!(token_service("another secret").verify(&token).is_err());
+    }
+}
@@ -0,0 +1,2 @@
+pub mod argon2_hasher;
+pub mod jwt_token_service;
@@ -0,0 +1,177 @@
+//! The storage backend picked by the `postgres`, `sqlite` and `memory` features, so that the
+//! applications declare their shaku module once whatever the backend:
+//!
+//! ```ignore
+//! use infrastructure::backend::{self, PostRepository, Store, UnitOfWork, UserRepository};
+//!
+//! module! {
+//!     AppModule {
+//!         components = [Store, ...],
+//!         providers = [UserRepository, PostRepository, UnitOfWork, ...]
+//!     }
+//! }
+//!
+//! let config = Config::load()?;
+//! let store = backend::connect(&config.database).await?;
+//! let module = AppModule::builder()
+//!     .with_component_parameters::<Store>(store)
+//!     .build();
+//! ```
+//!
+//! When several of them are enabled, `memory` wins over `sqlite` which wins over `postgres`.
+
+#[cfg(not(any(feature = "postgres", feature = "sqlite", feature = "memory")))]
+compile_error!("Enable one of the `postgres`, `sqlite` or `memory` features of `infrastructure`");
+
+#[cfg(feature = "memory")]
+pub use memory::*;
+#[cfg(all(feature = "postgres", not(any(feature = "sqlite", feature = "memory"))))]
+pub use postgres::*;
+#[cfg(all(feature = "sqlite", not(feature = "memory")))]
+pub use sqlite::*;
+
+#[cfg(feature = "postgres")]
+pub mod postgres {
+    use crate::{
+        config::DatabaseConfig,
+        libs::db::{
+            migrations::{self, errors},
+            service,
+        },
+        outbox::{Relay, RelayConfig, Sink},
+    };
+    use futures::future::{FutureExt, LocalBoxFuture};
+    use std::sync::Arc;
+
+    pub use crate::{
+        libs::db::{
+            service::{DBService as Store, DBServiceParameters as StoreParameters},
+            unit_of_work::UnitOfWork,
+        },
+        post::db_repository::DbRepository as PostRepository,
+        user::db_repository::DbRepository as UserRepository,
+    };
+
+    /// Fail on settings `connect` could not use, so that they are reported before anything starts.
+    pub fn check_config(config: &DatabaseConfig) -> Result<(), crate::config::errors::Load> {
+        config.pool_config().map(|_| ())
+    }
+
+    /// Connect to the database of `database.url`, migrated unless `database.run_migrations`
+    /// is off, and refuse a schema ahead of this binary.
+    pub async fn connect(config: &DatabaseConfig) -> Result<StoreParameters, anyhow::Error> {
+        let config = config.pool_config()?;
+        let pool = service::connect(&config).await?;
+        if config.run_migrations {
+            migrations::run_migrations(&pool).await?;
+        } else {
+            migrations::check_schema(&pool).await?;
+        }
+        Ok(StoreParameters { pool })
+    }
+
+    pub async fn run_migrations(store: &StoreParameters) -> Result<(), errors::Migrate> {
+        migrations::run_migrations(&store.pool).await
+    }
+
+    /// Deliver the outbox messages to the sink, until the process stops.
+    pub fn relay(
+        store: &StoreParameters,
+        sink: Arc<dyn Sink>,
+    ) -> Option<LocalBoxFuture<'static, ()>> {
+        Some(
+            Relay::new(store.pool.clone(), sink, RelayConfig::default())
+                .run()
+                .boxed_local(),
+        )
+    }
+}
+
+#[cfg(feature = "sqlite")]
+pub mod sqlite {
+    use crate::{
+        config::DatabaseConfig,
+        libs::{db::migrations::errors, sqlite::service},
+        outbox::Sink,
+    };
+    use futures::future::LocalBoxFuture;
+    use std::sync::Arc;
+
+    pub use crate::{
+        libs::sqlite::{
+            service::{DBService as Store, DBServiceParameters as StoreParameters},
+            unit_of_work::UnitOfWork,
+        },
+        post::sqlite_repository::DbRepository as PostRepository,
+        user::sqlite_repository::DbRepository as UserRepository,
+    };
+
+    /// Fail on settings `connect` could not use, so that they are reported before anything starts.
+    pub fn check_config(config: &DatabaseConfig) -> Result<(), crate::config::errors::Load> {
+        config.pool_config().map(|_| ())
+    }
+
+    /// Open the database file of `database.url`, created when missing and migrated unless
+    /// `database.run_migrations` is off, and refuse a schema ahead of this binary.
+    pub async fn connect(config: &DatabaseConfig) -> Result<StoreParameters, anyhow::Error> {
+        let config = config.pool_config()?;
+        let pool = service::connect(&config).await?;
+        if config.run_migrations {
+            service::run_migrations(&pool).await?;
+        } else {
+            service::check_schema(&pool).await?;
+        }
+        Ok(StoreParameters { pool })
+    }
+
+    pub async fn run_migrations(store: &StoreParameters) -> Result<(), errors::Migrate> {
+        service::run_migrations(&store.pool).await
+    }
+
+    /// The relay only reads the Postgres outbox, SQLite messages stay in their table.
+    pub fn relay(
+        _store: &StoreParameters,
+        _sink: Arc<dyn Sink>,
+    ) -> Option<LocalBoxFuture<'static, ()>> {
+        None
+    }
+}
+
+#[cfg(feature = "memory")]
+pub mod memory {
+    use crate::{config::DatabaseConfig, libs::db::migrations::errors, outbox::Sink};
+    use futures::future::LocalBoxFuture;
+    use std::sync::Arc;
+
+    pub use crate::{
+        libs::memory::{
+            store::{MemoryStore as Store, MemoryStoreParameters as StoreParameters},
+            unit_of_work::MemoryUnitOfWork as UnitOfWork,
+        },
+        post::memory_repository::MemoryRepository as PostRepository,
+        user::memory_repository::MemoryRepository as UserRepository,
+    };
+
+    /// Any settings will do, they are unused.
+    pub fn check_config(_config: &DatabaseConfig) -> Result<(), crate::config::errors::Load> {
+        Ok(())
+    }
+
+    /// An empty store, nothing is kept once the process stops and the settings are unused.
+    pub async fn connect(_config: &DatabaseConfig) -> Result<StoreParameters, anyhow::Error> {
+        Ok(StoreParameters::default())
+    }
+
+    /// There is no schema to migrate.
+    pub async fn run_migrations(_store: &StoreParameters) -> Result<(), errors::Migrate> {
+        Ok(())
+    }
+
+    /// Nothing is recorded in an outbox, there is nothing to relay.
+    pub fn relay(
+        _store: &StoreParameters,
+        _sink: Arc<dyn Sink>,
+    ) -> Option<LocalBoxFuture<'static, ()>> {
+        None
+    }
+}
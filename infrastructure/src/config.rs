@@ -0,0 +1,443 @@
+//! Settings of the applications, layered: the defaults, then the TOML file, then the environment.
+//!
+//! The file is the one named by `CONFIG_FILE`, or `config.toml` in the working directory when it
+//! exists. Every setting can be overridden by an environment variable (or the `.env` file) named
+//! after its section and key, `database.max_connections` by `DATABASE_MAX_CONNECTIONS` for instance.
+
+use crate::{auth::jwt_token_service::TokenConfig, libs::db::config::PoolConfig};
+use dotenv::dotenv;
+use serde::Deserialize;
+use std::{env, path::Path, str::FromStr, time::Duration};
+
+const DEFAULT_FILE: &str = "config.toml";
+
+pub mod errors {
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Load {
+        #[error("Cannot read the configuration file {path}: {source}")]
+        Read {
+            path: String,
+            source: std::io::Error,
+        },
+
+        #[error("Invalid configuration file {path}: {source}")]
+        Parse {
+            path: String,
+            source: toml::de::Error,
+        },
+
+        #[error("{name} has an invalid value: \"{value}\"")]
+        Env { name: &'static str, value: String },
+
+        #[error("Invalid configuration:\n  - {}", .0.join("\n  - "))]
+        Invalid(Vec<String>),
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub auth: AuthConfig,
+    pub server: ServerConfig,
+    pub log: LogConfig,
+    pub graphql: GraphqlConfig,
+}
+
+/// Durations are in seconds.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// Not needed by the `memory` backend.
+    pub url: Option<String>,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: u64,
+    /// `0` keeps the idle connections open.
+    pub idle_timeout: u64,
+    /// Apply the pending migrations at startup, a schema ahead of the binary is refused anyway.
+    pub run_migrations: bool,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        DatabaseConfig {
+            url: None,
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: 30,
+            idle_timeout: 600,
+            run_migrations: true,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    pub fn pool_config(&self) -> Result<PoolConfig, errors::Load> {
+        let database_url = self.url.clone().ok_or_else(|| {
+            errors::Load::Invalid(vec!["database.url (DATABASE_URL) must be set".into()])
+        })?;
+
+        Ok(PoolConfig {
+            database_url,
+            max_connections: self.max_connections,
+            min_connections: self.min_connections,
+            acquire_timeout: Duration::from_secs(self.acquire_timeout),
+            idle_timeout: match self.idle_timeout {
+                0 => None,
+                idle_timeout => Some(Duration::from_secs(idle_timeout)),
+            },
+            run_migrations: self.run_migrations,
+        })
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// Not needed by the applications which do not issue session tokens.
+    pub token_secret: Option<String>,
+    /// In seconds.
+    pub token_ttl: u64,
+}
+
+/// The secret is redacted, the configuration is printed when it fails to load.
+impl std::fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthConfig")
+            .field(
+                "token_secret",
+                &self.token_secret.as_ref().map(|_| "<redacted>"),
+            )
+            .field("token_ttl", &self.token_ttl)
+            .finish()
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig {
+            token_secret: None,
+            token_ttl: 24 * 60 * 60,
+        }
+    }
+}
+
+impl AuthConfig {
+    pub fn token_config(&self) -> Result<TokenConfig, errors::Load> {
+        let secret = self.token_secret.clone().ok_or_else(|| {
+            errors::Load::Invalid(vec![
+                "auth.token_secret (AUTH_TOKEN_SECRET) must be set".into()
+            ])
+        })?;
+
+        Ok(TokenConfig {
+            secret,
+            ttl: Duration::from_secs(self.token_ttl),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            host: "127.0.0.1".into(),
+            port: 8000,
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Bunyan JSON lines, for the log collectors.
+    Json,
+    /// Human readable lines, for the terminal.
+    Pretty,
+}
+
+impl FromStr for LogFormat {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(LogFormat::Json),
+            "pretty" => Ok(LogFormat::Pretty),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    /// A `tracing` filter such as `info` or `info,sqlx=warn`, `RUST_LOG` takes precedence.
+    pub level: String,
+    pub format: LogFormat,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            level: "info".into(),
+            format: LogFormat::Json,
+        }
+    }
+}
+
+/// Limits protecting the server from expensive queries.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GraphqlConfig {
+    pub max_depth: usize,
+    pub max_complexity: usize,
+}
+
+impl Default for GraphqlConfig {
+    fn default() -> Self {
+        GraphqlConfig {
+            max_depth: 16,
+            max_complexity: 1000,
+        }
+    }
+}
+
+impl Config {
+    /// Load and validate the configuration, to be done once at startup.
+    pub fn load() -> Result<Config, errors::Load> {
+        dotenv().ok();
+        let mut config = match env::var("CONFIG_FILE") {
+            Ok(path) => Config::from_file(&path)?,
+            Err(_) if Path::new(DEFAULT_FILE).exists() => Config::from_file(DEFAULT_FILE)?,
+            Err(_) => Config::default(),
+        };
+        config.override_with(|name| env::var(name).ok())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn from_file(path: &str) -> Result<Config, errors::Load> {
+        let content = std::fs::read_to_string(path).map_err(|source| errors::Load::Read {
+            path: path.into(),
+            source,
+        })?;
+
+        toml::from_str(&content).map_err(|source| errors::Load::Parse {
+            path: path.into(),
+            source,
+        })
+    }
+
+    /// Override the settings with the variables given by `var`.
+    fn override_with(&mut self, var: impl Fn(&str) -> Option<String>) -> Result<(), errors::Load> {
+        fn set<T: FromStr>(
+            var: &impl Fn(&str) -> Option<String>,
+            name: &'static str,
+            setting: &mut T,
+        ) -> Result<(), errors::Load> {
+            if let Some(value) = var(name) {
+                *setting = value
+                    .parse()
+                    .map_err(|_| errors::Load::Env { name, value })?;
+            }
+            Ok(())
+        }
+
+        if let Some(url) = var("DATABASE_URL") {
+            self.database.url = Some(url);
+        }
+        set(
+            &var,
+            "DATABASE_MAX_CONNECTIONS",
+            &mut self.database.max_connections,
+        )?;
+        set(
+            &var,
+            "DATABASE_MIN_CONNECTIONS",
+            &mut self.database.min_connections,
+        )?;
+        set(
+            &var,
+            "DATABASE_ACQUIRE_TIMEOUT",
+            &mut self.database.acquire_timeout,
+        )?;
+        set(
+            &var,
+            "DATABASE_IDLE_TIMEOUT",
+            &mut self.database.idle_timeout,
+        )?;
+        set(
+            &var,
+            "DATABASE_RUN_MIGRATIONS",
+            &mut self.database.run_migrations,
+        )?;
+        if let Some(secret) = var("AUTH_TOKEN_SECRET") {
+            self.auth.token_secret = Some(secret);
+        }
+        set(&var, "AUTH_TOKEN_TTL", &mut self.auth.token_ttl)?;
+        set(&var, "SERVER_HOST", &mut self.server.host)?;
+        set(&var, "SERVER_PORT", &mut self.server.port)?;
+        set(&var, "LOG_LEVEL", &mut self.log.level)?;
+        set(&var, "LOG_FORMAT", &mut self.log.format)?;
+        set(&var, "GRAPHQL_MAX_DEPTH", &mut self.graphql.max_depth)?;
+        set(
+            &var,
+            "GRAPHQL_MAX_COMPLEXITY",
+            &mut self.graphql.max_complexity,
+        )?;
+        Ok(())
+    }
+
+    /// Report every invalid setting at once rather than one per run.
+    fn validate(&self) -> Result<(), errors::Load> {
+        let checks = [
+            (
+                self.database.url.as_deref() != Some(""),
+                "database.url cannot be empty",
+            ),
+            (
+                self.database.max_connections > 0,
+                "database.max_connections must be at least 1",
+            ),
+            (
+                self.database.min_connections <= self.database.max_connections,
+                "database.min_connections cannot exceed database.max_connections",
+            ),
+            (
+                self.database.acquire_timeout > 0,
+                "database.acquire_timeout must be at least 1 second",
+            ),
+            (
+                self.auth.token_secret.as_deref() != Some(""),
+                "auth.token_secret cannot be empty",
+            ),
+            (
+                self.auth.token_ttl > 0,
+                "auth.token_ttl must be at least 1 second",
+            ),
+            (!self.server.host.is_empty(), "server.host cannot be empty"),
+            (!self.log.level.is_empty(), "log.level cannot be empty"),
+            (
+                self.graphql.max_depth > 0,
+                "graphql.max_depth must be at least 1",
+            ),
+            (
+                self.graphql.max_complexity > 0,
+                "graphql.max_complexity must be at least 1",
+            ),
+        ];
+
+        let problems: Vec<String> = checks
+            .iter()
+            .filter(|(valid, _)| !valid)
+            .map(|(_, problem)| (*problem).to_owned())
+            .collect();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(errors::Load::Invalid(problems))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{errors::Load, Config, LogFormat};
+    use std::collections::HashMap;
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = pairs
+            .iter()
+            .map(|(name, value)| ((*name).into(), (*value).into()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn it_fills_the_settings_missing_from_the_file_with_the_defaults() {
+        let config: Config = toml::from_str(
+            r#"
+            [server]
+            port = 8080
+
+            [log]
+            format = "pretty"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.server.address(), "127.0.0.1:8080");
+        assert_eq!(config.log.format, LogFormat::Pretty);
+        assert_eq!(config.database.max_connections, 10);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn it_rejects_unknown_settings() {
+        assert!(toml::from_str::<Config>("[server]\nprot = 8080").is_err());
+    }
+
+    #[test]
+    fn it_lets_the_environment_override_the_file() {
+        let mut config: Config = toml::from_str("[database]\nmax_connections = 5").unwrap();
+        config
+            .override_with(vars(&[
+                ("DATABASE_URL", "postgres://user@localhost/db"),
+                ("DATABASE_MAX_CONNECTIONS", "20"),
+                ("DATABASE_IDLE_TIMEOUT", "0"),
+            ]))
+            .unwrap();
+
+        let pool_config = config.database.pool_config().unwrap();
+        assert_eq!(pool_config.database_url, "postgres://user@localhost/db");
+        assert_eq!(pool_config.max_connections, 20);
+        assert_eq!(pool_config.idle_timeout, None);
+    }
+
+    #[test]
+    fn it_names_the_variable_with_an_invalid_value() {
+        let result = Config::default().override_with(vars(&[("SERVER_PORT", "http")]));
+        assert!(matches!(
+            result,
+            Err(Load::Env {
+                name: "SERVER_PORT",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn it_reports_every_invalid_setting() {
+        let mut config = Config::default();
+        config.database.min_connections = 20;
+        config.graphql.max_depth = 0;
+
+        match config.validate() {
+            Err(Load::Invalid(problems)) => assert_eq!(problems.len(), 2),
+            result => panic!("Unexpected {:?}", result),
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_token_secret_out_of_the_debug_output() {
+        let config: Config = toml::from_str("[auth]\ntoken_secret = \"fsociety\"").unwrap();
+
+        assert!(!format!("{:?}", config).contains("fsociety"));
+        assert!(!format!("{:?}", config.auth.token_config().unwrap()).contains("fsociety"));
+    }
+}
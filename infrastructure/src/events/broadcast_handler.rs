@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use domain::events::{DomainEvent, EventHandler};
+use futures::{stream, Stream};
+use tokio::sync::broadcast::{self, RecvError};
+
+/// Fan out the dispatched events to any number of live listeners (GraphQL subscriptions...).
+///
+/// Listeners too slow to keep up with `capacity` events miss the oldest ones.
+#[derive(Debug)]
+pub struct BroadcastHandler {
+    sender: broadcast::Sender<DomainEvent>,
+}
+
+impl BroadcastHandler {
+    pub fn new(capacity: usize) -> BroadcastHandler {
+        let (sender, _) = broadcast::channel(capacity);
+        BroadcastHandler { sender }
+    }
+
+    /// Stream of the events dispatched from now on.
+    pub fn events(&self) -> impl Stream<Item = DomainEvent> + Send {
+        stream::unfold(self.sender.subscribe(), |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Events listener lagging, events skipped")
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl EventHandler for BroadcastHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), anyhow::Error> {
+        // Sending only fails when nobody listens, which is fine.
+        let _ = self.sender.send(event.clone());
+        Ok(())
+    }
+}
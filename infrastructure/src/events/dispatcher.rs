@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use domain::events::{DispatcherInterface, DomainEvent, EventHandler};
+use shaku::Component;
+use std::sync::{Arc, RwLock};
+
+/// Dispatch the events to the handlers subscribed in the same process, one after the other.
+#[derive(Component)]
+#[shaku(interface = DispatcherInterface)]
+pub struct InProcessDispatcher {
+    handlers: RwLock<Vec<Arc<dyn EventHandler>>>,
+}
+
+impl std::fmt::Debug for InProcessDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InProcessDispatcher").finish()
+    }
+}
+
+#[async_trait]
+impl DispatcherInterface for InProcessDispatcher {
+    fn subscribe(&self, handler: Arc<dyn EventHandler>) {
+        self.handlers
+            .write()
+            .expect("Event handlers lock poisoned")
+            .push(handler);
+    }
+
+    #[tracing::instrument(skip(events))]
+    async fn dispatch(&self, events: Vec<DomainEvent>) {
+        let handlers = self
+            .handlers
+            .read()
+            .expect("Event handlers lock poisoned")
+            .clone();
+
+        for event in &events {
+            for handler in &handlers {
+                if let Err(err) = handler.handle(event).await {
+                    tracing::error!(event = event.name(), error = %err, "Event handler failed");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InProcessDispatcher;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use domain::{
+        events::{DispatcherInterface, DomainEvent, EventHandler},
+        user::User,
+    };
+    use shaku::{module, HasComponent};
+    use std::sync::{Arc, Mutex};
+
+    module! {
+        TestModule {
+            components = [InProcessDispatcher],
+            providers = []
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingHandler {
+        handled: Mutex<Vec<&'static str>>,
+    }
+
+    #[async_trait]
+    impl EventHandler for RecordingHandler {
+        async fn handle(&self, event: &DomainEvent) -> Result<(), anyhow::Error> {
+            self.handled.lock().unwrap().push(event.name());
+            Ok(())
+        }
+    }
+
+    struct FailingHandler;
+
+    #[async_trait]
+    impl EventHandler for FailingHandler {
+        async fn handle(&self, _event: &DomainEvent) -> Result<(), anyhow::Error> {
+            Err(anyhow::anyhow!("Boom"))
+        }
+    }
+
+    #[tokio::test]
+    async fn it_dispatches_to_every_subscribed_handler() {
+        let module = TestModule::builder().build();
+        let dispatcher: &dyn DispatcherInterface = module.resolve_ref();
+
+        let first = Arc::new(RecordingHandler::default());
+        let second = Arc::new(RecordingHandler::default());
+        dispatcher.subscribe(first.clone());
+        dispatcher.subscribe(Arc::new(FailingHandler));
+        dispatcher.subscribe(second.clone());
+
+        let mut user = User::new("Elliot".parse().unwrap(), Utc::now());
+        dispatcher.dispatch(user.take_events()).await;
+
+        assert_eq!(*first.handled.lock().unwrap(), vec!["UserCreated"]);
+        assert_eq!(*second.handled.lock().unwrap(), vec!["UserCreated"]);
+    }
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+use domain::events::{DomainEvent, EventHandler};
+
+/// Trace every dispatched event, handy to follow what happens in the logs.
+#[derive(Debug, Default)]
+pub struct LoggingHandler;
+
+#[async_trait]
+impl EventHandler for LoggingHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), anyhow::Error> {
+        tracing::info!(event = event.name(), "{:?}", event);
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod broadcast_handler;
+pub mod dispatcher;
+pub mod logging_handler;
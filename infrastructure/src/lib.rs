@@ -1,3 +1,8 @@
+pub mod auth;
+pub mod backend;
+pub mod config;
+pub mod events;
 pub mod libs;
+pub mod outbox;
 pub mod post;
 pub mod user;
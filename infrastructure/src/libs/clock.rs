@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use domain::clock::ClockInterface;
+use shaku::Component;
+
+#[derive(Component)]
+#[shaku(interface = ClockInterface)]
+pub struct SystemClock {}
+
+impl ClockInterface for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
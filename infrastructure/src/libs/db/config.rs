@@ -0,0 +1,14 @@
+use std::time::Duration;
+
+/// Settings used to build the shared pool of the Postgres or SQLite backend, see
+/// `config::DatabaseConfig::pool_config`.
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    pub database_url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout: Duration,
+    pub idle_timeout: Option<Duration>,
+    /// Apply the pending migrations at startup, a schema ahead of the binary is refused anyway.
+    pub run_migrations: bool,
+}
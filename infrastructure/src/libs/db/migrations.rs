@@ -0,0 +1,112 @@
+//! The SQL of `migrations` is embedded in the binary, so that the applications bring the schema up
+//! to date at startup instead of relying on `sqlx-cli`.
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use sqlx::migrate::Migrator;
+#[cfg(feature = "postgres")]
+use sqlx::{pool::Pool, postgres::Postgres};
+
+pub mod errors {
+    use thiserror::Error;
+
+    #[derive(Error, Debug)]
+    pub enum Migrate {
+        #[error(
+            "The database schema is at version {applied}, ahead of this binary which only knows \
+             the migrations up to version {latest}"
+        )]
+        SchemaAhead { applied: i64, latest: i64 },
+
+        #[error(transparent)]
+        Other(#[from] anyhow::Error),
+    }
+}
+
+/// A binary must not run on a schema migrated by a newer one, it would ignore its changes.
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+pub(crate) fn check_not_ahead(
+    migrator: &Migrator,
+    applied: Option<i64>,
+) -> Result<(), errors::Migrate> {
+    let latest = migrator
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or_default();
+
+    compare_versions(latest, applied)
+}
+
+#[cfg(any(test, feature = "postgres", feature = "sqlite"))]
+fn compare_versions(latest: i64, applied: Option<i64>) -> Result<(), errors::Migrate> {
+    match applied {
+        Some(applied) if applied > latest => Err(errors::Migrate::SchemaAhead { applied, latest }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(feature = "postgres")]
+pub fn migrator() -> Migrator {
+    sqlx::migrate!("./migrations")
+}
+
+#[cfg(feature = "postgres")]
+async fn applied_version(pool: &Pool<Postgres>) -> Result<Option<i64>, sqlx::Error> {
+    let has_migrations: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+    if !has_migrations {
+        return Ok(None);
+    }
+
+    sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+        .fetch_one(pool)
+        .await
+}
+
+/// Fail if the schema is ahead of the migrations embedded in this binary.
+#[cfg(feature = "postgres")]
+pub async fn check_schema(pool: &Pool<Postgres>) -> Result<(), errors::Migrate> {
+    let applied = applied_version(pool)
+        .await
+        .map_err(|err| errors::Migrate::Other(err.into()))?;
+
+    check_not_ahead(&migrator(), applied)
+}
+
+/// Apply the pending migrations, unless the schema is ahead of this binary.
+#[cfg(feature = "postgres")]
+pub async fn run_migrations(pool: &Pool<Postgres>) -> Result<(), errors::Migrate> {
+    check_schema(pool).await?;
+
+    migrator()
+        .run(pool)
+        .await
+        .map_err(|err| errors::Migrate::Other(err.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compare_versions, errors::Migrate};
+
+    const LATEST: i64 = 20210307141208;
+
+    #[test]
+    fn it_accepts_a_schema_up_to_date_or_behind() {
+        assert!(compare_versions(LATEST, None).is_ok());
+        assert!(compare_versions(LATEST, Some(20201202225410)).is_ok());
+        assert!(compare_versions(LATEST, Some(LATEST)).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_a_schema_ahead_of_the_binary() {
+        assert!(matches!(
+            compare_versions(LATEST, Some(20210314120000)),
+            Err(Migrate::SchemaAhead {
+                applied: 20210314120000,
+                latest: LATEST,
+            })
+        ));
+    }
+}
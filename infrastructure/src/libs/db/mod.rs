@@ -1 +1,13 @@
+//! The Postgres backend, behind the `postgres` feature, and what the SQL backends share.
+
+pub mod config;
+pub mod migrations;
+#[cfg(feature = "postgres")]
 pub mod service;
+#[cfg(feature = "postgres")]
+pub mod unit_of_work;
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+pub(crate) fn transaction_finished() -> anyhow::Error {
+    anyhow::anyhow!("The transaction is already committed or rolled back")
+}
@@ -1,27 +1,41 @@
-use async_trait::async_trait;
-use dotenv::dotenv;
-use shaku::Provider;
+use super::config::PoolConfig;
+use shaku::{Component, Interface};
 use sqlx::{
     error::Error,
     pool::Pool,
-    postgres::{PgPool, Postgres},
+    postgres::{PgPoolOptions, Postgres},
 };
-use std::env;
 
-#[async_trait]
-pub trait DBServiceInterface {
-    async fn executor(&self) -> Result<Pool<Postgres>, Error>;
+pub trait DBServiceInterface: Interface {
+    fn pool(&self) -> &Pool<Postgres>;
 }
 
-#[derive(Provider)]
-#[shaku(interface = DBServiceInterface + Send + Sync)]
-pub struct DBService {}
+/// Build the pool once, it is then shared by every repository through the `DBService` component.
+pub async fn connect(config: &PoolConfig) -> Result<Pool<Postgres>, Error> {
+    PgPoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .connect_timeout(config.acquire_timeout)
+        .idle_timeout(config.idle_timeout)
+        .connect(&config.database_url)
+        .await
+}
+
+#[derive(Component)]
+#[shaku(interface = DBServiceInterface)]
+pub struct DBService {
+    #[shaku(no_default)]
+    pool: Pool<Postgres>,
+}
+
+impl DBService {
+    pub fn new(pool: Pool<Postgres>) -> DBService {
+        DBService { pool }
+    }
+}
 
-#[async_trait]
 impl DBServiceInterface for DBService {
-    async fn executor(&self) -> Result<Pool<Postgres>, Error> {
-        dotenv().ok();
-        let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-        PgPool::connect(&database_url).await
+    fn pool(&self) -> &Pool<Postgres> {
+        &self.pool
     }
 }
@@ -0,0 +1,100 @@
+use super::{service::DBServiceInterface, transaction_finished};
+use crate::{post, user};
+use async_trait::async_trait;
+use domain::{
+    post::RepositoryInterface as PostRepositoryInterface,
+    unit_of_work::{errors, Transaction, UnitOfWorkInterface},
+    user::RepositoryInterface as UserRepositoryInterface,
+};
+use shaku::Provider;
+use sqlx::postgres::Postgres;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Transaction shared by the repositories of a unit of work, `None` once it is finished.
+pub type SharedTransaction = Arc<Mutex<Option<sqlx::Transaction<'static, Postgres>>>>;
+
+#[derive(Provider)]
+#[shaku(interface = UnitOfWorkInterface + Send + Sync)]
+pub struct UnitOfWork {
+    #[shaku(inject)]
+    db_service: Arc<dyn DBServiceInterface>,
+}
+
+impl std::fmt::Debug for UnitOfWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnitOfWork").finish()
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkInterface for UnitOfWork {
+    #[tracing::instrument]
+    async fn begin(&self) -> Result<Box<dyn Transaction>, errors::Begin> {
+        let transaction = self
+            .db_service
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| errors::Begin::Other(err.into()))?;
+
+        Ok(Box::new(DbTransaction::new(Arc::new(Mutex::new(Some(
+            transaction,
+        ))))))
+    }
+}
+
+pub struct DbTransaction {
+    transaction: SharedTransaction,
+    users: user::db_repository::TransactionRepository,
+    posts: post::db_repository::TransactionRepository,
+}
+
+impl DbTransaction {
+    fn new(transaction: SharedTransaction) -> DbTransaction {
+        DbTransaction {
+            users: user::db_repository::TransactionRepository::new(transaction.clone()),
+            posts: post::db_repository::TransactionRepository::new(transaction.clone()),
+            transaction,
+        }
+    }
+}
+
+#[async_trait]
+impl Transaction for DbTransaction {
+    fn users(&self) -> &dyn UserRepositoryInterface {
+        &self.users
+    }
+
+    fn posts(&self) -> &dyn PostRepositoryInterface {
+        &self.posts
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), errors::Commit> {
+        let transaction = self
+            .transaction
+            .lock()
+            .await
+            .take()
+            .ok_or_else(|| errors::Commit::Other(transaction_finished()))?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| errors::Commit::Other(err.into()))
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), errors::Rollback> {
+        let transaction = self
+            .transaction
+            .lock()
+            .await
+            .take()
+            .ok_or_else(|| errors::Rollback::Other(transaction_finished()))?;
+
+        transaction
+            .rollback()
+            .await
+            .map_err(|err| errors::Rollback::Other(err.into()))
+    }
+}
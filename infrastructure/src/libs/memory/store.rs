@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use domain::user::Role;
+use shaku::{Component, Interface};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// A user as kept by the in-memory store, mirroring a row of the `users` table.
+#[derive(Clone, Debug)]
+pub struct UserRow {
+    pub id: u128,
+    pub nickname: String,
+    pub nickname_key: String,
+    pub password_hash: Option<String>,
+    pub role: Role,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i32,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// A post as kept by the in-memory store, mirroring a row of the `posts` table.
+#[derive(Clone, Debug)]
+pub struct PostRow {
+    pub id: u128,
+    pub author_id: u128,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i32,
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Rows are keyed by id, so iterating a table gives the same order as `ORDER BY id` in Postgres.
+#[derive(Clone, Debug, Default)]
+pub struct Tables {
+    pub users: BTreeMap<u128, UserRow>,
+    pub posts: BTreeMap<u128, PostRow>,
+    generation: u64,
+}
+
+impl Tables {
+    /// Bumped on every write, a unit of work uses it to detect concurrent writers.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub(crate) fn written(&mut self) {
+        self.generation += 1;
+    }
+}
+
+pub type SharedTables = Arc<Mutex<Tables>>;
+
+pub(crate) fn lock(tables: &SharedTables) -> Result<MutexGuard<'_, Tables>, anyhow::Error> {
+    tables
+        .lock()
+        .map_err(|_| anyhow::anyhow!("The memory store was poisoned by a panicking writer"))
+}
+
+pub trait MemoryStoreInterface: Interface {
+    fn tables(&self) -> &SharedTables;
+}
+
+/// Keeps users and posts in memory, in place of `DBService`, for tests and demos.
+///
+/// Register it along with the `MemoryRepository` providers and `MemoryUnitOfWork` instead of
+/// the Postgres ones. Nothing is persisted and no event is recorded in the outbox.
+#[derive(Component, Default)]
+#[shaku(interface = MemoryStoreInterface)]
+pub struct MemoryStore {
+    tables: SharedTables,
+}
+
+impl MemoryStore {
+    pub(crate) fn with_tables(tables: Tables) -> MemoryStore {
+        MemoryStore {
+            tables: Arc::new(Mutex::new(tables)),
+        }
+    }
+}
+
+impl std::fmt::Debug for MemoryStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryStore").finish()
+    }
+}
+
+impl MemoryStoreInterface for MemoryStore {
+    fn tables(&self) -> &SharedTables {
+        &self.tables
+    }
+}
@@ -0,0 +1,163 @@
+use super::store::{lock, MemoryStore, MemoryStoreInterface, Tables};
+use crate::{post, user};
+use async_trait::async_trait;
+use domain::{
+    post::RepositoryInterface as PostRepositoryInterface,
+    unit_of_work::{errors, Transaction, UnitOfWorkInterface},
+    user::RepositoryInterface as UserRepositoryInterface,
+};
+use shaku::Provider;
+use std::sync::Arc;
+
+#[derive(Provider)]
+#[shaku(interface = UnitOfWorkInterface + Send + Sync)]
+pub struct MemoryUnitOfWork {
+    #[shaku(inject)]
+    store: Arc<dyn MemoryStoreInterface>,
+}
+
+impl std::fmt::Debug for MemoryUnitOfWork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryUnitOfWork").finish()
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkInterface for MemoryUnitOfWork {
+    #[tracing::instrument]
+    async fn begin(&self) -> Result<Box<dyn Transaction>, errors::Begin> {
+        let snapshot = lock(self.store.tables())?.clone();
+
+        Ok(Box::new(MemoryTransaction::new(
+            self.store.clone(),
+            snapshot,
+        )))
+    }
+}
+
+/// Works on a copy of the tables, swapped into the store on commit.
+///
+/// The commit fails if anything else wrote to the store since the transaction began,
+/// so that a transaction never overwrites writes it has not seen.
+pub struct MemoryTransaction {
+    store: Arc<dyn MemoryStoreInterface>,
+    generation: u64,
+    working_copy: Arc<MemoryStore>,
+    users: user::memory_repository::MemoryRepository,
+    posts: post::memory_repository::MemoryRepository,
+}
+
+impl MemoryTransaction {
+    fn new(store: Arc<dyn MemoryStoreInterface>, snapshot: Tables) -> Self {
+        let generation = snapshot.generation();
+        let working_copy = Arc::new(MemoryStore::with_tables(snapshot));
+        MemoryTransaction {
+            users: user::memory_repository::MemoryRepository::new(working_copy.clone()),
+            posts: post::memory_repository::MemoryRepository::new(working_copy.clone()),
+            store,
+            generation,
+            working_copy,
+        }
+    }
+}
+
+#[async_trait]
+impl Transaction for MemoryTransaction {
+    fn users(&self) -> &dyn UserRepositoryInterface {
+        &self.users
+    }
+
+    fn posts(&self) -> &dyn PostRepositoryInterface {
+        &self.posts
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), errors::Commit> {
+        let mut tables = lock(self.store.tables())?;
+        if tables.generation() != self.generation {
+            return Err(errors::Commit::Other(anyhow::anyhow!(
+                "The memory store was written to since the transaction began"
+            )));
+        }
+
+        // The working copy started at the same generation and bumped it on each of its writes.
+        *tables = lock(self.working_copy.tables())?.clone();
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), errors::Rollback> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::store::MemoryStore;
+    use super::MemoryUnitOfWork;
+    use chrono::Utc;
+    use domain::{
+        unit_of_work::UnitOfWorkInterface,
+        user::{errors, User},
+    };
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn it_only_applies_the_writes_once_committed() {
+        let unit_of_work = MemoryUnitOfWork {
+            store: Arc::new(MemoryStore::default()),
+        };
+        let user = User::new("Elliot".parse().unwrap(), Utc::now());
+
+        let transaction = unit_of_work.begin().await.unwrap();
+        transaction.users().save(&user).await.unwrap();
+        let other = unit_of_work.begin().await.unwrap();
+        assert!(matches!(
+            other.users().find(user.id()).await,
+            Err(errors::Find::NotFound)
+        ));
+        transaction.commit().await.unwrap();
+
+        let transaction = unit_of_work.begin().await.unwrap();
+        assert!(transaction.users().find(user.id()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_discards_the_writes_when_rolled_back() {
+        let unit_of_work = MemoryUnitOfWork {
+            store: Arc::new(MemoryStore::default()),
+        };
+        let user = User::new("Elliot".parse().unwrap(), Utc::now());
+
+        let transaction = unit_of_work.begin().await.unwrap();
+        transaction.users().save(&user).await.unwrap();
+        transaction.rollback().await.unwrap();
+
+        let transaction = unit_of_work.begin().await.unwrap();
+        assert!(matches!(
+            transaction.users().find(user.id()).await,
+            Err(errors::Find::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_fails_to_commit_over_a_concurrent_write() {
+        let unit_of_work = MemoryUnitOfWork {
+            store: Arc::new(MemoryStore::default()),
+        };
+
+        let first = unit_of_work.begin().await.unwrap();
+        let second = unit_of_work.begin().await.unwrap();
+        first
+            .users()
+            .save(&User::new("Elliot".parse().unwrap(), Utc::now()))
+            .await
+            .unwrap();
+        second
+            .users()
+            .save(&User::new("Darlene".parse().unwrap(), Utc::now()))
+            .await
+            .unwrap();
+
+        assert!(first.commit().await.is_ok());
+        assert!(second.commit().await.is_err());
+    }
+}
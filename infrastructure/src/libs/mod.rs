@@ -1 +1,6 @@
+pub mod clock;
 pub mod db;
+#[cfg(any(test, feature = "memory"))]
+pub mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
@@ -0,0 +1,109 @@
+use crate::libs::db::{
+    config::PoolConfig,
+    migrations::{check_not_ahead, errors},
+};
+use shaku::{Component, Interface};
+use sqlx::{
+    error::Error,
+    migrate::Migrator,
+    pool::Pool,
+    sqlite::{Sqlite, SqliteConnectOptions, SqlitePoolOptions},
+};
+use std::str::FromStr;
+
+pub trait DBServiceInterface: Interface {
+    fn pool(&self) -> &Pool<Sqlite>;
+}
+
+/// Build the pool once, it is then shared by every repository through the `DBService` component.
+///
+/// `DATABASE_URL` is a file (`sqlite://blog.db`), created when missing. An in-memory database
+/// (`sqlite::memory:`) only lives as long as its connection, so it needs a single connection.
+pub async fn connect(config: &PoolConfig) -> Result<Pool<Sqlite>, Error> {
+    let options = SqliteConnectOptions::from_str(&config.database_url)?
+        .create_if_missing(true)
+        .foreign_keys(true);
+
+    SqlitePoolOptions::new()
+        .max_connections(config.max_connections)
+        .min_connections(config.min_connections)
+        .connect_timeout(config.acquire_timeout)
+        .idle_timeout(config.idle_timeout)
+        .connect_with(options)
+        .await
+}
+
+/// The embedded `migrations_sqlite`, the SQLite counterpart of `migrations`.
+pub fn migrator() -> Migrator {
+    sqlx::migrate!("./migrations_sqlite")
+}
+
+async fn applied_version(pool: &Pool<Sqlite>) -> Result<Option<i64>, Error> {
+    let has_migrations: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+    )
+    .fetch_one(pool)
+    .await?;
+    if !has_migrations {
+        return Ok(None);
+    }
+
+    sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+        .fetch_one(pool)
+        .await
+}
+
+/// Fail if the schema is ahead of the migrations embedded in this binary.
+pub async fn check_schema(pool: &Pool<Sqlite>) -> Result<(), errors::Migrate> {
+    let applied = applied_version(pool)
+        .await
+        .map_err(|err| errors::Migrate::Other(err.into()))?;
+
+    check_not_ahead(&migrator(), applied)
+}
+
+/// Apply the pending migrations, unless the schema is ahead of this binary.
+pub async fn run_migrations(pool: &Pool<Sqlite>) -> Result<(), errors::Migrate> {
+    check_schema(pool).await?;
+
+    migrator()
+        .run(pool)
+        .await
+        .map_err(|err| errors::Migrate::Other(err.into()))
+}
+
+#[derive(Component)]
+#[shaku(interface = DBServiceInterface)]
+pub struct DBService {
+    #[shaku(no_default)]
+    pool: Pool<Sqlite>,
+}
+
+impl DBService {
+    pub fn new(pool: Pool<Sqlite>) -> DBService {
+        DBService { pool }
+    }
+}
+
+impl DBServiceInterface for DBService {
+    fn pool(&self) -> &Pool<Sqlite> {
+        &self.pool
+    }
+}
+
+/// A migrated in-memory database, gone once the pool is dropped.
+#[cfg(test)]
+pub(crate) async fn connect_in_memory() -> Pool<Sqlite> {
+    let pool = connect(&PoolConfig {
+        database_url: "sqlite::memory:".into(),
+        max_connections: 1,
+        min_connections: 1,
+        acquire_timeout: std::time::Duration::from_secs(30),
+        idle_timeout: None,
+        run_migrations: true,
+    })
+    .await
+    .unwrap();
+    run_migrations(&pool).await.unwrap();
+    pool
+}
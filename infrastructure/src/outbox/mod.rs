@@ -0,0 +1,11 @@
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+mod record;
+#[cfg(feature = "postgres")]
+mod relay;
+mod sink;
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+pub(crate) use record::*;
+#[cfg(feature = "postgres")]
+pub use relay::*;
+pub use sink::*;
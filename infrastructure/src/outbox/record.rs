@@ -0,0 +1,66 @@
+use domain::events::DomainEvent;
+use serde_json::{json, Value};
+#[cfg(feature = "postgres")]
+use sqlx::postgres::PgConnection;
+#[cfg(feature = "sqlite")]
+use sqlx::sqlite::SqliteConnection;
+
+fn payload(event: &DomainEvent) -> Value {
+    match event {
+        DomainEvent::UserCreated {
+            id,
+            nickname,
+            created_at,
+        } => json!({
+            "id": id.to_string(),
+            "nickname": nickname,
+            "created_at": created_at.to_rfc3339(),
+        }),
+        DomainEvent::PostCreated {
+            id,
+            author_id,
+            content,
+            created_at,
+        } => json!({
+            "id": id.to_string(),
+            "author_id": author_id.to_string(),
+            "content": content,
+            "created_at": created_at.to_rfc3339(),
+        }),
+    }
+}
+
+/// Store the events in the outbox, to be called with the connection of the transaction
+/// persisting the entity so that both are written or neither is.
+#[cfg(feature = "postgres")]
+pub(crate) async fn record(
+    conn: &mut PgConnection,
+    events: &[DomainEvent],
+) -> Result<(), sqlx::Error> {
+    for event in events {
+        sqlx::query!(
+            "INSERT INTO outbox (event_type, payload) VALUES ($1, $2)",
+            event.name(),
+            payload(event)
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// SQLite counterpart of `record`, the relay only reads the Postgres outbox for now.
+#[cfg(feature = "sqlite")]
+pub(crate) async fn record_sqlite(
+    conn: &mut SqliteConnection,
+    events: &[DomainEvent],
+) -> Result<(), sqlx::Error> {
+    for event in events {
+        sqlx::query("INSERT INTO outbox (event_type, payload) VALUES (?1, ?2)")
+            .bind(event.name())
+            .bind(payload(event).to_string())
+            .execute(&mut *conn)
+            .await?;
+    }
+    Ok(())
+}
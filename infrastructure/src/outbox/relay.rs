@@ -0,0 +1,142 @@
+use super::sink::{Message, Sink};
+use sqlx::{pool::Pool, postgres::Postgres};
+use std::{sync::Arc, time::Duration};
+
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+    pub batch_size: u32,
+    pub poll_interval: Duration,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Messages failing this many times are left aside (and kept in the table for inspection).
+    pub max_attempts: i32,
+}
+
+impl Default for RelayConfig {
+    fn default() -> Self {
+        RelayConfig {
+            batch_size: 50,
+            poll_interval: Duration::from_secs(1),
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(300),
+            max_attempts: 10,
+        }
+    }
+}
+
+impl RelayConfig {
+    /// Exponential backoff: `base_backoff * 2^(attempts - 1)`, capped to `max_backoff`.
+    pub fn backoff(&self, attempts: i32) -> Duration {
+        let exponent = (attempts.max(1) - 1).min(16) as u32;
+        self.base_backoff
+            .checked_mul(2u32.pow(exponent))
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+/// Read the pending outbox messages, deliver them to the sink and mark them as sent.
+pub struct Relay {
+    pool: Pool<Postgres>,
+    sink: Arc<dyn Sink>,
+    config: RelayConfig,
+}
+
+impl std::fmt::Debug for Relay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutboxRelay")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl Relay {
+    pub fn new(pool: Pool<Postgres>, sink: Arc<dyn Sink>, config: RelayConfig) -> Relay {
+        Relay { pool, sink, config }
+    }
+
+    /// Relay the messages until the process stops.
+    pub async fn run(self) {
+        loop {
+            match self.relay_batch().await {
+                Ok(count) if count == self.config.batch_size as usize => continue,
+                Ok(_) => {}
+                Err(err) => tracing::error!(error = %err, "Outbox relay failed"),
+            }
+            tokio::time::delay_for(self.config.poll_interval).await;
+        }
+    }
+
+    /// Deliver one batch of pending messages, returns how many were handled.
+    ///
+    /// Rows are locked with `SKIP LOCKED` so several relays can run side by side.
+    #[tracing::instrument]
+    pub async fn relay_batch(&self) -> Result<usize, sqlx::Error> {
+        let mut transaction = self.pool.begin().await?;
+
+        let messages = sqlx::query_as!(
+            Message,
+            r#"SELECT id, event_type, payload, attempts FROM outbox
+               WHERE published_at IS NULL AND next_attempt_at <= NOW() AND attempts < $2
+               ORDER BY id
+               LIMIT $1
+               FOR UPDATE SKIP LOCKED"#,
+            self.config.batch_size as i64,
+            self.config.max_attempts
+        )
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        for message in &messages {
+            match self.sink.deliver(message).await {
+                Ok(()) => {
+                    sqlx::query!(
+                        "UPDATE outbox SET published_at = NOW(), attempts = attempts + 1 WHERE id = $1",
+                        message.id
+                    )
+                    .execute(&mut *transaction)
+                    .await?;
+                }
+                Err(err) => {
+                    tracing::warn!(id = message.id, error = %err, "Outbox message delivery failed");
+                    let backoff = self.config.backoff(message.attempts + 1);
+                    sqlx::query!(
+                        r#"UPDATE outbox
+                           SET attempts = attempts + 1,
+                               last_error = $2,
+                               next_attempt_at = NOW() + make_interval(secs => $3::FLOAT8)
+                           WHERE id = $1"#,
+                        message.id,
+                        err.to_string(),
+                        backoff.as_secs_f64()
+                    )
+                    .execute(&mut *transaction)
+                    .await?;
+                }
+            }
+        }
+
+        transaction.commit().await?;
+        Ok(messages.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RelayConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn it_doubles_the_backoff_up_to_the_max() {
+        let config = RelayConfig {
+            base_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+            ..RelayConfig::default()
+        };
+
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(2), Duration::from_secs(2));
+        assert_eq!(config.backoff(4), Duration::from_secs(8));
+        assert_eq!(config.backoff(5), Duration::from_secs(10));
+        assert_eq!(config.backoff(100), Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// A row of the outbox waiting to be delivered.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub id: i64,
+    pub event_type: String,
+    pub payload: Value,
+    pub attempts: i32,
+}
+
+/// Where the relay delivers the outbox messages (a message broker, a webhook...).
+///
+/// Delivery is at least once: a message may be delivered again if the relay stops
+/// before marking it as sent.
+#[async_trait]
+pub trait Sink: Send + Sync {
+    async fn deliver(&self, message: &Message) -> Result<(), anyhow::Error>;
+}
+
+/// Only trace the messages, useful until a real broker is plugged.
+#[derive(Debug, Default)]
+pub struct LoggingSink;
+
+#[async_trait]
+impl Sink for LoggingSink {
+    async fn deliver(&self, message: &Message) -> Result<(), anyhow::Error> {
+        tracing::info!(
+            id = message.id,
+            event = message.event_type.as_str(),
+            payload = %message.payload,
+            "Outbox message delivered"
+        );
+        Ok(())
+    }
+}
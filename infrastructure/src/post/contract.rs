@@ -0,0 +1,159 @@
+//! Behaviour every `post::RepositoryInterface` implementation must share.
+//!
+//! Run it against a backend with `post_repository_contract!(factory)`, where `factory` is an
+//! async function returning a fresh user repository and post repository of the same store, the
+//! user one being needed to save the authors.
+
+use crate::user::contract::unique_nickname;
+use chrono::Utc;
+use domain::{
+    libs::id::Identifier,
+    post::{errors, Id, Post, RepositoryInterface},
+    user::{self, RepositoryInterface as UserRepositoryInterface, User},
+};
+
+async fn save_author(users: &dyn UserRepositoryInterface) -> User {
+    let author = User::new(unique_nickname("Elliot"), Utc::now());
+    users.save(&author).await.unwrap();
+    author
+}
+
+/// Whether `list` returns the post, going through the pages up to where its id would be.
+async fn is_listed(repository: &dyn RepositoryInterface, id: &Id) -> bool {
+    let mut after = None;
+    loop {
+        let page = repository.list(after.as_ref(), 100).await.unwrap();
+        if page.items.iter().any(|post| post.id() == id) {
+            return true;
+        }
+        match page.items.last() {
+            Some(last) if page.has_next_page && last.id().inner_value() < id.inner_value() => {
+                after = Some(*last.id())
+            }
+            _ => return false,
+        }
+    }
+}
+
+pub(crate) async fn it_finds_a_saved_post(
+    users: &dyn UserRepositoryInterface,
+    posts: &dyn RepositoryInterface,
+) {
+    let author = save_author(users).await;
+    let post = Post::new(*author.id(), "My post".parse().unwrap(), Utc::now());
+    posts.save(&post).await.unwrap();
+
+    let found = posts.find(post.id()).await.unwrap();
+    assert_eq!(found.id(), post.id());
+    assert_eq!(found.author_id(), author.id());
+    assert_eq!(found.content(), "My post");
+    assert_eq!(found.version(), 1);
+    assert!(found.events().is_empty());
+}
+
+pub(crate) async fn it_does_not_find_an_unknown_post(
+    _users: &dyn UserRepositoryInterface,
+    posts: &dyn RepositoryInterface,
+) {
+    assert!(matches!(
+        posts.find(&Id::generate()).await,
+        Err(errors::Find::NotFound)
+    ));
+}
+
+pub(crate) async fn it_rejects_a_post_of_an_unknown_author(
+    _users: &dyn UserRepositoryInterface,
+    posts: &dyn RepositoryInterface,
+) {
+    let post = Post::new(user::Id::generate(), "My post".parse().unwrap(), Utc::now());
+    assert!(matches!(
+        posts.save(&post).await,
+        Err(errors::Save::AuthorNotFound)
+    ));
+}
+
+pub(crate) async fn it_does_not_update_an_unknown_post(
+    users: &dyn UserRepositoryInterface,
+    posts: &dyn RepositoryInterface,
+) {
+    let author = save_author(users).await;
+    let post = Post::new(*author.id(), "My post".parse().unwrap(), Utc::now());
+    assert!(matches!(
+        posts.update(&post).await,
+        Err(errors::Update::NotFound)
+    ));
+}
+
+pub(crate) async fn it_does_not_update_a_post_changed_since_it_was_loaded(
+    users: &dyn UserRepositoryInterface,
+    posts: &dyn RepositoryInterface,
+) {
+    let author = save_author(users).await;
+    let post = Post::new(*author.id(), "My post".parse().unwrap(), Utc::now());
+    posts.save(&post).await.unwrap();
+
+    let mut first = posts.find(post.id()).await.unwrap();
+    let mut second = posts.find(post.id()).await.unwrap();
+    first.edit("My first edit".parse().unwrap(), Utc::now());
+    second.edit("My second edit".parse().unwrap(), Utc::now());
+    posts.update(&first).await.unwrap();
+
+    assert!(matches!(
+        posts.update(&second).await,
+        Err(errors::Update::Conflict)
+    ));
+    assert_eq!(
+        posts.find(post.id()).await.unwrap().content(),
+        "My first edit"
+    );
+}
+
+pub(crate) async fn it_hides_soft_deleted_posts(
+    users: &dyn UserRepositoryInterface,
+    posts: &dyn RepositoryInterface,
+) {
+    let author = save_author(users).await;
+    let mut post = Post::new(*author.id(), "My post".parse().unwrap(), Utc::now());
+    posts.save(&post).await.unwrap();
+    post.delete(Utc::now());
+    posts.update(&post).await.unwrap();
+
+    assert!(matches!(
+        posts.find(post.id()).await,
+        Err(errors::Find::NotFound)
+    ));
+    assert!(posts
+        .find_including_deleted(post.id())
+        .await
+        .unwrap()
+        .is_deleted());
+    assert!(!is_listed(posts, post.id()).await);
+}
+
+/// Generate a test per contract case, each one running against repositories built by `$factory`.
+///
+/// Attributes given before the factory, `#[ignore]` for instance, are applied to every test.
+macro_rules! post_repository_contract {
+    ($(#[$attribute:meta])* $factory:path) => {
+        $crate::post::contract::post_repository_contract!(@cases [$(#[$attribute])*] $factory;
+            it_finds_a_saved_post,
+            it_does_not_find_an_unknown_post,
+            it_rejects_a_post_of_an_unknown_author,
+            it_does_not_update_an_unknown_post,
+            it_does_not_update_a_post_changed_since_it_was_loaded,
+            it_hides_soft_deleted_posts,
+        );
+    };
+    (@cases [$(#[$attribute:meta])*] $factory:path; $($case:ident,)*) => {
+        $(
+            #[tokio::test]
+            $(#[$attribute])*
+            async fn $case() {
+                let (users, posts) = $factory().await;
+                $crate::post::contract::$case(&users, &posts).await;
+            }
+        )*
+    };
+}
+
+pub(crate) use post_repository_contract;
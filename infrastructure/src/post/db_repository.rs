@@ -1,11 +1,22 @@
-use crate::libs::db::service::DBServiceInterface;
+use crate::{
+    libs::db::{
+        service::DBServiceInterface, transaction_finished, unit_of_work::SharedTransaction,
+    },
+    outbox,
+};
 use async_trait::async_trait;
 use domain::{
-    libs::id::Identifier,
+    libs::{id::Identifier, pagination::Page},
     post::{errors, Id, Post, RepositoryInterface},
+    user::Id as UserId,
 };
 use shaku::Provider;
-use sqlx::{error::Error as sqlxError, types::Uuid};
+use sqlx::{
+    error::Error as sqlxError,
+    postgres::{PgConnection, PgDatabaseError},
+    types::Uuid,
+};
+use std::sync::Arc;
 
 fn to_pg_uuid(id: &Id) -> Uuid {
     Uuid::from_u128(id.inner_value())
@@ -15,11 +26,141 @@ fn from_pg_uuid(pg_uuid: Uuid) -> Id {
     Id::new(pg_uuid.as_u128())
 }
 
+async fn find(
+    conn: &mut PgConnection,
+    id: &Id,
+    include_deleted: bool,
+) -> Result<Post, errors::Find> {
+    sqlx::query!(
+        "SELECT * FROM posts WHERE id = $1 AND ($2 OR deleted_at IS NULL)",
+        to_pg_uuid(id),
+        include_deleted
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        sqlxError::RowNotFound => errors::Find::NotFound,
+        _ => errors::Find::Other(err.into()),
+    })
+    .map(|record| {
+        Post::build_from_repository(
+            from_pg_uuid(record.id),
+            UserId::new(record.author_id.as_u128()),
+            record.content,
+            record.created_at,
+            record.updated_at,
+            record.version,
+            record.deleted_at,
+        )
+    })
+}
+
+async fn list(
+    conn: &mut PgConnection,
+    after: Option<&Id>,
+    limit: usize,
+) -> Result<Page<Post>, errors::List> {
+    let records = sqlx::query!(
+        r#"SELECT * FROM posts
+           WHERE deleted_at IS NULL AND ($1::UUID IS NULL OR id > $1)
+           ORDER BY id
+           LIMIT $2"#,
+        after.map(to_pg_uuid),
+        limit as i64 + 1
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|err| errors::List::Other(err.into()))?;
+
+    let items = records
+        .into_iter()
+        .map(|record| {
+            Post::build_from_repository(
+                from_pg_uuid(record.id),
+                UserId::new(record.author_id.as_u128()),
+                record.content,
+                record.created_at,
+                record.updated_at,
+                record.version,
+                record.deleted_at,
+            )
+        })
+        .collect();
+
+    Ok(Page::from_items(items, limit))
+}
+
+async fn exists(conn: &mut PgConnection, id: &Id) -> Result<bool, sqlxError> {
+    let record = sqlx::query!(
+        r#"SELECT EXISTS(SELECT 1 FROM posts WHERE id = $1) AS "exists!""#,
+        to_pg_uuid(id)
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(record.exists)
+}
+
+async fn save(conn: &mut PgConnection, post: &Post) -> Result<(), errors::Save> {
+    sqlx::query!(
+        r#"INSERT INTO posts (id, author_id, content, created_at, updated_at, version)
+           VALUES ($1, $2, $3, $4, $5, $6)"#,
+        to_pg_uuid(post.id()),
+        Uuid::from_u128(post.author_id().inner_value()),
+        post.content(),
+        post.created_at(),
+        post.updated_at(),
+        post.version()
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        sqlxError::Database(db_err)
+            if Some("posts_author_id_fkey")
+                == db_err.downcast_ref::<PgDatabaseError>().constraint() =>
+        {
+            errors::Save::AuthorNotFound
+        }
+        err => errors::Save::Other(err.into()),
+    })?;
+
+    outbox::record(conn, post.events())
+        .await
+        .map_err(|err| errors::Save::Other(err.into()))
+}
+
+async fn update(conn: &mut PgConnection, post: &Post) -> Result<(), errors::Update> {
+    let done = sqlx::query!(
+        r#"UPDATE posts SET content = $2, updated_at = $3, version = $4, deleted_at = $5
+           WHERE id = $1 AND version = $6"#,
+        to_pg_uuid(post.id()),
+        post.content(),
+        post.updated_at(),
+        post.version(),
+        post.deleted_at(),
+        post.loaded_version()
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|err| errors::Update::Other(err.into()))?;
+
+    if done.rows_affected() > 0 {
+        return Ok(());
+    }
+
+    // Nothing was updated: either the post is gone or another writer bumped its version.
+    match exists(conn, post.id()).await {
+        Ok(true) => Err(errors::Update::Conflict),
+        Ok(false) => Err(errors::Update::NotFound),
+        Err(err) => Err(errors::Update::Other(err.into())),
+    }
+}
+
 #[derive(Provider)]
 #[shaku(interface = RepositoryInterface + Send + Sync)]
 pub struct DbRepository {
-    #[shaku(provide)]
-    db_service: Box<dyn DBServiceInterface + Send + Sync>,
+    #[shaku(inject)]
+    db_service: Arc<dyn DBServiceInterface>,
 }
 
 impl std::fmt::Debug for DbRepository {
@@ -32,38 +173,163 @@ impl std::fmt::Debug for DbRepository {
 impl RepositoryInterface for DbRepository {
     #[tracing::instrument]
     async fn find(&self, id: &Id) -> Result<Post, errors::Find> {
-        let executor = &self
+        let mut conn = self
             .db_service
-            .executor()
+            .pool()
+            .acquire()
             .await
             .map_err(|err| errors::Find::Other(err.into()))?;
 
-        sqlx::query!("SELECT * FROM posts WHERE id = $1", to_pg_uuid(id))
-            .fetch_one(executor)
+        find(&mut conn, id, false).await
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<Post, errors::Find> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
             .await
-            .map_err(|err: sqlxError| match err {
-                sqlxError::RowNotFound => errors::Find::NotFound,
-                _ => errors::Find::Other(err.into()),
-            })
-            .map(|record| Post::build_from_repository(from_pg_uuid(record.id), record.content))
+            .map_err(|err| errors::Find::Other(err.into()))?;
+
+        find(&mut conn, id, true).await
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<Post>, errors::List> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::List::Other(err.into()))?;
+
+        list(&mut conn, after, limit).await
     }
 
     #[tracing::instrument]
     async fn save(&self, post: &Post) -> Result<(), errors::Save> {
-        let executor = &self
+        let mut transaction = self
             .db_service
-            .executor()
+            .pool()
+            .begin()
             .await
             .map_err(|err| errors::Save::Other(err.into()))?;
 
-        sqlx::query!(
-            "INSERT INTO posts (id, content) VALUES ($1, $2)",
-            to_pg_uuid(post.id()),
-            post.content()
+        save(&mut transaction, post).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| errors::Save::Other(err.into()))
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, post: &Post) -> Result<(), errors::Update> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Update::Other(err.into()))?;
+
+        update(&mut conn, post).await
+    }
+}
+
+/// Repository running its queries inside the transaction of a unit of work.
+pub struct TransactionRepository {
+    transaction: SharedTransaction,
+}
+
+impl TransactionRepository {
+    pub(crate) fn new(transaction: SharedTransaction) -> TransactionRepository {
+        TransactionRepository { transaction }
+    }
+}
+
+impl std::fmt::Debug for TransactionRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostTransactionRepository").finish()
+    }
+}
+
+#[async_trait]
+impl RepositoryInterface for TransactionRepository {
+    #[tracing::instrument]
+    async fn find(&self, id: &Id) -> Result<Post, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find(conn, id, false).await
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<Post, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find(conn, id, true).await
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<Post>, errors::List> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::List::Other(transaction_finished()))?;
+
+        list(conn, after, limit).await
+    }
+
+    #[tracing::instrument]
+    async fn save(&self, post: &Post) -> Result<(), errors::Save> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Save::Other(transaction_finished()))?;
+
+        save(conn, post).await
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, post: &Post) -> Result<(), errors::Update> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Update::Other(transaction_finished()))?;
+
+        update(conn, post).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DbRepository;
+    use crate::{
+        config::Config,
+        libs::db::service::{self, DBService, DBServiceInterface},
+        post::contract::post_repository_contract,
+        user,
+    };
+    use std::sync::Arc;
+
+    async fn repositories() -> (user::db_repository::DbRepository, DbRepository) {
+        let config = Config::load().unwrap().database.pool_config().unwrap();
+        let pool = service::connect(&config).await.unwrap();
+        let db_service: Arc<dyn DBServiceInterface> = Arc::new(DBService::new(pool));
+        (
+            user::db_repository::DbRepository::new(db_service.clone()),
+            DbRepository { db_service },
         )
-        .execute(executor)
-        .await
-        .map_err(|err| errors::Save::Other(err.into()))
-        .and(Ok(()))
     }
+
+    post_repository_contract!(
+        #[ignore = "needs the migrated Postgres database of database.url"]
+        repositories
+    );
 }
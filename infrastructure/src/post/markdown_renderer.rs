@@ -0,0 +1,45 @@
+use domain::post::RendererInterface;
+use pulldown_cmark::{html, Options, Parser};
+use shaku::Component;
+
+/// Renders CommonMark, then strips anything unsafe (scripts, event handlers, ...) from the HTML.
+#[derive(Component)]
+#[shaku(interface = RendererInterface)]
+pub struct MarkdownRenderer {}
+
+impl RendererInterface for MarkdownRenderer {
+    fn render_html(&self, content: &str) -> String {
+        let parser = Parser::new_ext(
+            content,
+            Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TABLES,
+        );
+        let mut unsafe_html = String::new();
+        html::push_html(&mut unsafe_html, parser);
+        ammonia::clean(&unsafe_html)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MarkdownRenderer;
+    use domain::post::RendererInterface;
+
+    #[test]
+    fn it_renders_markdown() {
+        let renderer = MarkdownRenderer {};
+
+        assert_eq!(
+            renderer.render_html("Hello *friend*"),
+            "<p>Hello <em>friend</em></p>\n"
+        );
+    }
+
+    #[test]
+    fn it_sanitizes_the_html() {
+        let renderer = MarkdownRenderer {};
+
+        let html = renderer.render_html("<script>alert(1)</script>[link](javascript:alert(1))");
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("javascript:"));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::libs::memory::store::{lock, MemoryStoreInterface, PostRow, Tables};
+use async_trait::async_trait;
+use domain::{
+    libs::{id::Identifier, pagination::Page},
+    post::{errors, Id, Post, RepositoryInterface},
+    user::Id as UserId,
+};
+use shaku::Provider;
+use std::{ops::Bound, sync::Arc};
+
+impl PostRow {
+    fn from_post(post: &Post) -> PostRow {
+        PostRow {
+            id: post.id().inner_value(),
+            author_id: post.author_id().inner_value(),
+            content: post.content().into(),
+            created_at: *post.created_at(),
+            updated_at: *post.updated_at(),
+            version: post.version(),
+            deleted_at: post.deleted_at().copied(),
+        }
+    }
+
+    fn to_post(&self) -> Post {
+        Post::build_from_repository(
+            Id::new(self.id),
+            UserId::new(self.author_id),
+            self.content.clone(),
+            self.created_at,
+            self.updated_at,
+            self.version,
+            self.deleted_at,
+        )
+    }
+}
+
+fn find(tables: &Tables, id: &Id, include_deleted: bool) -> Result<Post, errors::Find> {
+    tables
+        .posts
+        .get(&id.inner_value())
+        .filter(|row| include_deleted || row.deleted_at.is_none())
+        .map(PostRow::to_post)
+        .ok_or(errors::Find::NotFound)
+}
+
+fn list(tables: &Tables, after: Option<&Id>, limit: usize) -> Page<Post> {
+    let start = after.map_or(Bound::Unbounded, |id| Bound::Excluded(id.inner_value()));
+    let items = tables
+        .posts
+        .range((start, Bound::Unbounded))
+        .map(|(_, row)| row)
+        .filter(|row| row.deleted_at.is_none())
+        .take(limit + 1)
+        .map(PostRow::to_post)
+        .collect();
+
+    Page::from_items(items, limit)
+}
+
+fn save(tables: &mut Tables, post: &Post) -> Result<(), errors::Save> {
+    let row = PostRow::from_post(post);
+    if tables.posts.contains_key(&row.id) {
+        return Err(errors::Save::Other(anyhow::anyhow!(
+            "Post {} already exists",
+            post.id()
+        )));
+    }
+    // Like the foreign key of the `posts` table, soft deleted authors still count.
+    if !tables.users.contains_key(&row.author_id) {
+        return Err(errors::Save::AuthorNotFound);
+    }
+
+    tables.posts.insert(row.id, row);
+    tables.written();
+    Ok(())
+}
+
+fn update(tables: &mut Tables, post: &Post) -> Result<(), errors::Update> {
+    match tables.posts.get(&post.id().inner_value()) {
+        None => Err(errors::Update::NotFound),
+        Some(stored) if stored.version != post.loaded_version() => Err(errors::Update::Conflict),
+        Some(_) => {
+            let row = PostRow::from_post(post);
+            tables.posts.insert(row.id, row);
+            tables.written();
+            Ok(())
+        }
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = RepositoryInterface + Send + Sync)]
+pub struct MemoryRepository {
+    #[shaku(inject)]
+    store: Arc<dyn MemoryStoreInterface>,
+}
+
+impl MemoryRepository {
+    pub(crate) fn new(store: Arc<dyn MemoryStoreInterface>) -> MemoryRepository {
+        MemoryRepository { store }
+    }
+}
+
+impl std::fmt::Debug for MemoryRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostMemoryRepository").finish()
+    }
+}
+
+#[async_trait]
+impl RepositoryInterface for MemoryRepository {
+    #[tracing::instrument]
+    async fn find(&self, id: &Id) -> Result<Post, errors::Find> {
+        find(&*lock(self.store.tables())?, id, false)
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<Post, errors::Find> {
+        find(&*lock(self.store.tables())?, id, true)
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<Post>, errors::List> {
+        Ok(list(&*lock(self.store.tables())?, after, limit))
+    }
+
+    #[tracing::instrument]
+    async fn save(&self, post: &Post) -> Result<(), errors::Save> {
+        save(&mut *lock(self.store.tables())?, post)
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, post: &Post) -> Result<(), errors::Update> {
+        update(&mut *lock(self.store.tables())?, post)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MemoryRepository;
+    use crate::{libs::memory::store::MemoryStore, post::contract::post_repository_contract, user};
+    use chrono::Utc;
+    use domain::{
+        post::{Post, RepositoryInterface},
+        user::{RepositoryInterface as UserRepositoryInterface, User},
+    };
+    use std::sync::Arc;
+
+    async fn repositories() -> (user::memory_repository::MemoryRepository, MemoryRepository) {
+        let store = Arc::new(MemoryStore::default());
+        (
+            user::memory_repository::MemoryRepository::new(store.clone()),
+            MemoryRepository::new(store),
+        )
+    }
+
+    post_repository_contract!(repositories);
+
+    #[tokio::test]
+    async fn it_lists_the_saved_posts_in_pages() {
+        let (users, repository) = repositories().await;
+        let author = User::new("Elliot".parse().unwrap(), Utc::now());
+        users.save(&author).await.unwrap();
+        for content in &["First", "Second", "Third"] {
+            let post = Post::new(*author.id(), content.parse().unwrap(), Utc::now());
+            repository.save(&post).await.unwrap();
+        }
+
+        let first_page = repository.list(None, 2).await.unwrap();
+        assert_eq!(first_page.items.len(), 2);
+        assert!(first_page.has_next_page);
+        let last_page = repository
+            .list(Some(first_page.items[1].id()), 2)
+            .await
+            .unwrap();
+        assert_eq!(last_page.items.len(), 1);
+        assert!(!last_page.has_next_page);
+    }
+}
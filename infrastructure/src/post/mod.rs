@@ -1 +1,10 @@
+#[cfg(feature = "postgres")]
 pub mod db_repository;
+pub mod markdown_renderer;
+#[cfg(any(test, feature = "memory"))]
+pub mod memory_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_repository;
+
+#[cfg(test)]
+pub(crate) mod contract;
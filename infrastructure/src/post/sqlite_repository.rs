@@ -0,0 +1,334 @@
+use crate::{
+    libs::{
+        db::transaction_finished,
+        sqlite::{service::DBServiceInterface, unit_of_work::SharedTransaction},
+    },
+    outbox,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::{
+    libs::{id::Identifier, pagination::Page},
+    post::{errors, Id, Post, RepositoryInterface},
+    user::Id as UserId,
+};
+use shaku::Provider;
+use sqlx::{error::Error as sqlxError, sqlite::SqliteConnection, types::Uuid, FromRow};
+use std::sync::Arc;
+
+fn to_sqlite_id(id: &Id) -> String {
+    Uuid::from_u128(id.inner_value()).to_string()
+}
+
+fn parse_uuid(sqlite_id: &str) -> Result<u128, anyhow::Error> {
+    Ok(Uuid::parse_str(sqlite_id)?.as_u128())
+}
+
+#[derive(FromRow)]
+struct PostRecord {
+    id: String,
+    author_id: String,
+    content: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl PostRecord {
+    fn into_post(self) -> Result<Post, anyhow::Error> {
+        Ok(Post::build_from_repository(
+            Id::new(parse_uuid(&self.id)?),
+            UserId::new(parse_uuid(&self.author_id)?),
+            self.content,
+            self.created_at,
+            self.updated_at,
+            self.version,
+            self.deleted_at,
+        ))
+    }
+}
+
+async fn find(
+    conn: &mut SqliteConnection,
+    id: &Id,
+    include_deleted: bool,
+) -> Result<Post, errors::Find> {
+    sqlx::query_as::<_, PostRecord>(
+        r#"SELECT id, author_id, content, created_at, updated_at, version, deleted_at
+           FROM posts
+           WHERE id = ?1 AND (?2 OR deleted_at IS NULL)"#,
+    )
+    .bind(to_sqlite_id(id))
+    .bind(include_deleted)
+    .fetch_one(conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        sqlxError::RowNotFound => errors::Find::NotFound,
+        _ => errors::Find::Other(err.into()),
+    })?
+    .into_post()
+    .map_err(errors::Find::Other)
+}
+
+async fn list(
+    conn: &mut SqliteConnection,
+    after: Option<&Id>,
+    limit: usize,
+) -> Result<Page<Post>, errors::List> {
+    let records = sqlx::query_as::<_, PostRecord>(
+        r#"SELECT id, author_id, content, created_at, updated_at, version, deleted_at
+           FROM posts
+           WHERE deleted_at IS NULL AND (?1 IS NULL OR id > ?1)
+           ORDER BY id
+           LIMIT ?2"#,
+    )
+    .bind(after.map(to_sqlite_id))
+    .bind(limit as i64 + 1)
+    .fetch_all(conn)
+    .await
+    .map_err(|err| errors::List::Other(err.into()))?;
+
+    let items = records
+        .into_iter()
+        .map(PostRecord::into_post)
+        .collect::<Result<Vec<Post>, anyhow::Error>>()
+        .map_err(errors::List::Other)?;
+
+    Ok(Page::from_items(items, limit))
+}
+
+async fn exists(conn: &mut SqliteConnection, id: &Id) -> Result<bool, sqlxError> {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM posts WHERE id = ?1)")
+        .bind(to_sqlite_id(id))
+        .fetch_one(conn)
+        .await
+}
+
+async fn save(conn: &mut SqliteConnection, post: &Post) -> Result<(), errors::Save> {
+    sqlx::query(
+        r#"INSERT INTO posts (id, author_id, content, created_at, updated_at, version)
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6)"#,
+    )
+    .bind(to_sqlite_id(post.id()))
+    .bind(Uuid::from_u128(post.author_id().inner_value()).to_string())
+    .bind(post.content())
+    .bind(post.created_at())
+    .bind(post.updated_at())
+    .bind(post.version())
+    .execute(&mut *conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        // SQLite does not tell which foreign key failed, posts only have the author one.
+        sqlxError::Database(db_err)
+            if db_err.message().contains("FOREIGN KEY constraint failed") =>
+        {
+            errors::Save::AuthorNotFound
+        }
+        err => errors::Save::Other(err.into()),
+    })?;
+
+    outbox::record_sqlite(conn, post.events())
+        .await
+        .map_err(|err| errors::Save::Other(err.into()))
+}
+
+async fn update(conn: &mut SqliteConnection, post: &Post) -> Result<(), errors::Update> {
+    let done = sqlx::query(
+        r#"UPDATE posts SET content = ?2, updated_at = ?3, version = ?4, deleted_at = ?5
+           WHERE id = ?1 AND version = ?6"#,
+    )
+    .bind(to_sqlite_id(post.id()))
+    .bind(post.content())
+    .bind(post.updated_at())
+    .bind(post.version())
+    .bind(post.deleted_at())
+    .bind(post.loaded_version())
+    .execute(&mut *conn)
+    .await
+    .map_err(|err| errors::Update::Other(err.into()))?;
+
+    if done.rows_affected() > 0 {
+        return Ok(());
+    }
+
+    // Nothing was updated: either the post is gone or another writer bumped its version.
+    match exists(conn, post.id()).await {
+        Ok(true) => Err(errors::Update::Conflict),
+        Ok(false) => Err(errors::Update::NotFound),
+        Err(err) => Err(errors::Update::Other(err.into())),
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = RepositoryInterface + Send + Sync)]
+pub struct DbRepository {
+    #[shaku(inject)]
+    db_service: Arc<dyn DBServiceInterface>,
+}
+
+impl std::fmt::Debug for DbRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostSqliteRepository").finish()
+    }
+}
+
+#[async_trait]
+impl RepositoryInterface for DbRepository {
+    #[tracing::instrument]
+    async fn find(&self, id: &Id) -> Result<Post, errors::Find> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Find::Other(err.into()))?;
+
+        find(&mut conn, id, false).await
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<Post, errors::Find> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Find::Other(err.into()))?;
+
+        find(&mut conn, id, true).await
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<Post>, errors::List> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::List::Other(err.into()))?;
+
+        list(&mut conn, after, limit).await
+    }
+
+    #[tracing::instrument]
+    async fn save(&self, post: &Post) -> Result<(), errors::Save> {
+        let mut transaction = self
+            .db_service
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| errors::Save::Other(err.into()))?;
+
+        save(&mut transaction, post).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| errors::Save::Other(err.into()))
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, post: &Post) -> Result<(), errors::Update> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Update::Other(err.into()))?;
+
+        update(&mut conn, post).await
+    }
+}
+
+/// Repository running its queries inside the transaction of a unit of work.
+pub struct TransactionRepository {
+    transaction: SharedTransaction,
+}
+
+impl TransactionRepository {
+    pub(crate) fn new(transaction: SharedTransaction) -> TransactionRepository {
+        TransactionRepository { transaction }
+    }
+}
+
+impl std::fmt::Debug for TransactionRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PostSqliteTransactionRepository").finish()
+    }
+}
+
+#[async_trait]
+impl RepositoryInterface for TransactionRepository {
+    #[tracing::instrument]
+    async fn find(&self, id: &Id) -> Result<Post, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find(conn, id, false).await
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<Post, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find(conn, id, true).await
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<Post>, errors::List> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::List::Other(transaction_finished()))?;
+
+        list(conn, after, limit).await
+    }
+
+    #[tracing::instrument]
+    async fn save(&self, post: &Post) -> Result<(), errors::Save> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Save::Other(transaction_finished()))?;
+
+        save(conn, post).await
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, post: &Post) -> Result<(), errors::Update> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Update::Other(transaction_finished()))?;
+
+        update(conn, post).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DbRepository;
+    use crate::{
+        libs::sqlite::service::{connect_in_memory, DBService, DBServiceInterface},
+        post::contract::post_repository_contract,
+        user,
+    };
+    use std::sync::Arc;
+
+    async fn repositories() -> (user::sqlite_repository::DbRepository, DbRepository) {
+        let db_service: Arc<dyn DBServiceInterface> =
+            Arc::new(DBService::new(connect_in_memory().await));
+        (
+            user::sqlite_repository::DbRepository::new(db_service.clone()),
+            DbRepository { db_service },
+        )
+    }
+
+    post_repository_contract!(repositories);
+}
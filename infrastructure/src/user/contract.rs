@@ -0,0 +1,180 @@
+//! Behaviour every `user::RepositoryInterface` implementation must share.
+//!
+//! Run it against a backend with `user_repository_contract!(factory)`, where `factory` is an
+//! async function returning a fresh repository. Nicknames are randomized so that the suite can
+//! run against a database that is not emptied between runs.
+
+use chrono::Utc;
+use domain::{
+    libs::id::Identifier,
+    user::{errors, nickname::Nickname, Id, RepositoryInterface, User},
+};
+
+pub(crate) fn unique_nickname(prefix: &str) -> Nickname {
+    format!("{}{}", prefix, rand::random::<u32>())
+        .parse()
+        .unwrap()
+}
+
+/// Whether `list` returns the user, going through the pages up to where its id would be.
+async fn is_listed(repository: &dyn RepositoryInterface, id: &Id) -> bool {
+    let mut after = None;
+    loop {
+        let page = repository.list(after.as_ref(), 100).await.unwrap();
+        if page.items.iter().any(|user| user.id() == id) {
+            return true;
+        }
+        match page.items.last() {
+            Some(last) if page.has_next_page && last.id().inner_value() < id.inner_value() => {
+                after = Some(*last.id())
+            }
+            _ => return false,
+        }
+    }
+}
+
+pub(crate) async fn it_finds_a_saved_user(repository: &dyn RepositoryInterface) {
+    let user = User::new(unique_nickname("Elliot"), Utc::now());
+    repository.save(&user).await.unwrap();
+
+    let found = repository.find(user.id()).await.unwrap();
+    assert_eq!(found.id(), user.id());
+    assert_eq!(found.nickname(), user.nickname());
+    assert_eq!(found.role(), user.role());
+    assert_eq!(found.version(), 1);
+    assert!(found.events().is_empty());
+}
+
+pub(crate) async fn it_does_not_find_an_unknown_user(repository: &dyn RepositoryInterface) {
+    assert!(matches!(
+        repository.find(&Id::generate()).await,
+        Err(errors::Find::NotFound)
+    ));
+}
+
+pub(crate) async fn it_finds_a_user_by_nickname_regardless_of_case(
+    repository: &dyn RepositoryInterface,
+) {
+    let user = User::new(unique_nickname("Elliot"), Utc::now());
+    repository.save(&user).await.unwrap();
+
+    let found = repository
+        .find_by_nickname(&user.nickname().to_uppercase())
+        .await
+        .unwrap();
+    assert_eq!(found.id(), user.id());
+}
+
+pub(crate) async fn it_does_not_find_an_unknown_nickname(repository: &dyn RepositoryInterface) {
+    assert!(matches!(
+        repository
+            .find_by_nickname(unique_nickname("Nobody").as_str())
+            .await,
+        Err(errors::Find::NotFound)
+    ));
+}
+
+pub(crate) async fn it_rejects_a_nickname_already_taken(repository: &dyn RepositoryInterface) {
+    let user = User::new(unique_nickname("Elliot"), Utc::now());
+    repository.save(&user).await.unwrap();
+
+    let homonym = User::new(user.nickname().to_lowercase().parse().unwrap(), Utc::now());
+    assert!(matches!(
+        repository.save(&homonym).await,
+        Err(errors::Save::NicknameExists { .. })
+    ));
+}
+
+pub(crate) async fn it_rejects_a_rename_to_a_nickname_already_taken(
+    repository: &dyn RepositoryInterface,
+) {
+    let elliot = User::new(unique_nickname("Elliot"), Utc::now());
+    let mut darlene = User::new(unique_nickname("Darlene"), Utc::now());
+    repository.save(&elliot).await.unwrap();
+    repository.save(&darlene).await.unwrap();
+
+    darlene.rename(elliot.nickname().parse().unwrap(), Utc::now());
+    assert!(matches!(
+        repository.update(&darlene).await,
+        Err(errors::Update::NicknameExists { .. })
+    ));
+}
+
+pub(crate) async fn it_does_not_update_an_unknown_user(repository: &dyn RepositoryInterface) {
+    let user = User::new(unique_nickname("Elliot"), Utc::now());
+    assert!(matches!(
+        repository.update(&user).await,
+        Err(errors::Update::NotFound)
+    ));
+}
+
+pub(crate) async fn it_does_not_update_a_user_changed_since_it_was_loaded(
+    repository: &dyn RepositoryInterface,
+) {
+    let user = User::new(unique_nickname("Elliot"), Utc::now());
+    repository.save(&user).await.unwrap();
+
+    let mut first = repository.find(user.id()).await.unwrap();
+    let mut second = repository.find(user.id()).await.unwrap();
+    first.rename(unique_nickname("Robot"), Utc::now());
+    second.rename(unique_nickname("Darlene"), Utc::now());
+    repository.update(&first).await.unwrap();
+
+    assert!(matches!(
+        repository.update(&second).await,
+        Err(errors::Update::Conflict)
+    ));
+    assert_eq!(repository.find(user.id()).await.unwrap().version(), 2);
+}
+
+pub(crate) async fn it_hides_soft_deleted_users(repository: &dyn RepositoryInterface) {
+    let mut user = User::new(unique_nickname("Elliot"), Utc::now());
+    repository.save(&user).await.unwrap();
+    user.delete(Utc::now());
+    repository.update(&user).await.unwrap();
+
+    assert!(matches!(
+        repository.find(user.id()).await,
+        Err(errors::Find::NotFound)
+    ));
+    assert!(matches!(
+        repository.find_by_nickname(user.nickname()).await,
+        Err(errors::Find::NotFound)
+    ));
+    assert!(repository
+        .find_including_deleted(user.id())
+        .await
+        .unwrap()
+        .is_deleted());
+    assert!(!is_listed(repository, user.id()).await);
+}
+
+/// Generate a test per contract case, each one running against a repository built by `$factory`.
+///
+/// Attributes given before the factory, `#[ignore]` for instance, are applied to every test.
+macro_rules! user_repository_contract {
+    ($(#[$attribute:meta])* $factory:path) => {
+        $crate::user::contract::user_repository_contract!(@cases [$(#[$attribute])*] $factory;
+            it_finds_a_saved_user,
+            it_does_not_find_an_unknown_user,
+            it_finds_a_user_by_nickname_regardless_of_case,
+            it_does_not_find_an_unknown_nickname,
+            it_rejects_a_nickname_already_taken,
+            it_rejects_a_rename_to_a_nickname_already_taken,
+            it_does_not_update_an_unknown_user,
+            it_does_not_update_a_user_changed_since_it_was_loaded,
+            it_hides_soft_deleted_users,
+        );
+    };
+    (@cases [$(#[$attribute:meta])*] $factory:path; $($case:ident,)*) => {
+        $(
+            #[tokio::test]
+            $(#[$attribute])*
+            async fn $case() {
+                $crate::user::contract::$case(&$factory().await).await;
+            }
+        )*
+    };
+}
+
+pub(crate) use user_repository_contract;
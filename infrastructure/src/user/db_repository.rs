@@ -1,11 +1,22 @@
-use crate::libs::db::service::DBServiceInterface;
+use crate::{
+    libs::db::{
+        service::DBServiceInterface, transaction_finished, unit_of_work::SharedTransaction,
+    },
+    outbox,
+};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use domain::{
-    libs::id::Identifier,
-    user::{errors, Id, RepositoryInterface, User},
+    libs::{id::Identifier, pagination::Page},
+    user::{errors, nickname, Id, RepositoryInterface, StoredUser, User},
 };
 use shaku::Provider;
-use sqlx::{error::Error as sqlxError, postgres::PgDatabaseError, types::Uuid};
+use sqlx::{
+    error::Error as sqlxError,
+    postgres::{PgConnection, PgDatabaseError},
+    types::Uuid,
+};
+use std::sync::Arc;
 
 fn to_pg_uuid(id: &Id) -> Uuid {
     Uuid::from_u128(id.inner_value())
@@ -15,11 +26,197 @@ fn from_pg_uuid(pg_uuid: Uuid) -> Id {
     Id::new(pg_uuid.as_u128())
 }
 
+struct UserRecord {
+    id: Uuid,
+    nickname: String,
+    password_hash: Option<String>,
+    role: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl UserRecord {
+    fn into_user(self) -> Result<User, anyhow::Error> {
+        Ok(User::build_from_repository(StoredUser {
+            id: from_pg_uuid(self.id),
+            nickname: self.nickname,
+            password_hash: self.password_hash,
+            role: self.role.parse().map_err(anyhow::Error::msg)?,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            version: self.version,
+            deleted_at: self.deleted_at,
+        }))
+    }
+}
+
+async fn find(
+    conn: &mut PgConnection,
+    id: &Id,
+    include_deleted: bool,
+) -> Result<User, errors::Find> {
+    sqlx::query_as!(
+        UserRecord,
+        r#"SELECT id, nickname, password_hash, role, created_at, updated_at, version, deleted_at
+           FROM users
+           WHERE id = $1 AND ($2 OR deleted_at IS NULL)"#,
+        to_pg_uuid(id),
+        include_deleted
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        sqlxError::RowNotFound => errors::Find::NotFound,
+        _ => errors::Find::Other(err.into()),
+    })?
+    .into_user()
+    .map_err(errors::Find::Other)
+}
+
+async fn list(
+    conn: &mut PgConnection,
+    after: Option<&Id>,
+    limit: usize,
+) -> Result<Page<User>, errors::List> {
+    let records = sqlx::query_as!(
+        UserRecord,
+        r#"SELECT id, nickname, password_hash, role, created_at, updated_at, version, deleted_at
+           FROM users
+           WHERE deleted_at IS NULL AND ($1::UUID IS NULL OR id > $1)
+           ORDER BY id
+           LIMIT $2"#,
+        after.map(to_pg_uuid),
+        limit as i64 + 1
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|err| errors::List::Other(err.into()))?;
+
+    let items = records
+        .into_iter()
+        .map(UserRecord::into_user)
+        .collect::<Result<Vec<User>, anyhow::Error>>()
+        .map_err(errors::List::Other)?;
+
+    Ok(Page::from_items(items, limit))
+}
+
+async fn find_by_nickname(conn: &mut PgConnection, nickname: &str) -> Result<User, errors::Find> {
+    sqlx::query_as!(
+        UserRecord,
+        r#"SELECT id, nickname, password_hash, role, created_at, updated_at, version, deleted_at
+           FROM users
+           WHERE nickname_key = $1 AND deleted_at IS NULL"#,
+        nickname::key(nickname)
+    )
+    .fetch_one(conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        sqlxError::RowNotFound => errors::Find::NotFound,
+        _ => errors::Find::Other(err.into()),
+    })?
+    .into_user()
+    .map_err(errors::Find::Other)
+}
+
+async fn exists(conn: &mut PgConnection, id: &Id) -> Result<bool, sqlxError> {
+    let record = sqlx::query!(
+        r#"SELECT EXISTS(SELECT 1 FROM users WHERE id = $1) AS "exists!""#,
+        to_pg_uuid(id)
+    )
+    .fetch_one(conn)
+    .await?;
+
+    Ok(record.exists)
+}
+
+fn is_nickname_taken(err: &sqlxError) -> bool {
+    match err {
+        sqlxError::Database(db_err) => {
+            Some("users_nickname_unique") == db_err.downcast_ref::<PgDatabaseError>().constraint()
+        }
+        _ => false,
+    }
+}
+
+async fn save(conn: &mut PgConnection, user: &User) -> Result<(), errors::Save> {
+    sqlx::query!(
+        r#"INSERT INTO users
+           (id, nickname, nickname_key, password_hash, role, created_at, updated_at, version)
+           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+        to_pg_uuid(user.id()),
+        user.nickname(),
+        nickname::key(user.nickname()),
+        user.password_hash(),
+        user.role().as_str(),
+        user.created_at(),
+        user.updated_at(),
+        user.version()
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        err if is_nickname_taken(&err) => errors::Save::NicknameExists {
+            nickname: user.nickname().into(),
+        },
+        err => errors::Save::Other(err.into()),
+    })?;
+
+    outbox::record(conn, user.events())
+        .await
+        .map_err(|err| errors::Save::Other(err.into()))
+}
+
+async fn update(conn: &mut PgConnection, user: &User) -> Result<(), errors::Update> {
+    let done = sqlx::query!(
+        r#"UPDATE users
+           SET nickname = $2, nickname_key = $3, password_hash = $4, role = $5, updated_at = $6,
+               version = $7, deleted_at = $8
+           WHERE id = $1 AND version = $9"#,
+        to_pg_uuid(user.id()),
+        user.nickname(),
+        nickname::key(user.nickname()),
+        user.password_hash(),
+        user.role().as_str(),
+        user.updated_at(),
+        user.version(),
+        user.deleted_at(),
+        user.loaded_version()
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        err if is_nickname_taken(&err) => errors::Update::NicknameExists {
+            nickname: user.nickname().into(),
+        },
+        err => errors::Update::Other(err.into()),
+    })?;
+
+    if done.rows_affected() > 0 {
+        return Ok(());
+    }
+
+    // Nothing was updated: either the user is gone or another writer bumped its version.
+    match exists(conn, user.id()).await {
+        Ok(true) => Err(errors::Update::Conflict),
+        Ok(false) => Err(errors::Update::NotFound),
+        Err(err) => Err(errors::Update::Other(err.into())),
+    }
+}
+
 #[derive(Provider)]
 #[shaku(interface = RepositoryInterface + Send + Sync)]
 pub struct DbRepository {
-    #[shaku(provide)]
-    db_service: Box<dyn DBServiceInterface + Send + Sync>,
+    #[shaku(inject)]
+    db_service: Arc<dyn DBServiceInterface>,
+}
+
+impl DbRepository {
+    pub fn new(db_service: Arc<dyn DBServiceInterface>) -> DbRepository {
+        DbRepository { db_service }
+    }
 }
 
 impl std::fmt::Debug for DbRepository {
@@ -32,66 +229,180 @@ impl std::fmt::Debug for DbRepository {
 impl RepositoryInterface for DbRepository {
     #[tracing::instrument]
     async fn find(&self, id: &Id) -> Result<User, errors::Find> {
-        let executor = &self
+        let mut conn = self
             .db_service
-            .executor()
+            .pool()
+            .acquire()
             .await
             .map_err(|err| errors::Find::Other(err.into()))?;
 
-        sqlx::query!("SELECT * FROM users WHERE id = $1", to_pg_uuid(id))
-            .fetch_one(executor)
+        find(&mut conn, id, false).await
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<User, errors::Find> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
             .await
-            .map_err(|err: sqlxError| match err {
-                sqlxError::RowNotFound => errors::Find::NotFound,
-                _ => errors::Find::Other(err.into()),
-            })
-            .map(|record| User::build_from_repository(from_pg_uuid(record.id), record.nickname))
+            .map_err(|err| errors::Find::Other(err.into()))?;
+
+        find(&mut conn, id, true).await
     }
 
     #[tracing::instrument]
     async fn find_by_nickname(&self, nickname: &str) -> Result<User, errors::Find> {
-        let executor = &self
+        let mut conn = self
             .db_service
-            .executor()
+            .pool()
+            .acquire()
             .await
             .map_err(|err| errors::Find::Other(err.into()))?;
 
-        sqlx::query!("SELECT * FROM users WHERE nickname = $1", nickname)
-            .fetch_one(executor)
+        find_by_nickname(&mut conn, nickname).await
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<User>, errors::List> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
             .await
-            .map_err(|err: sqlxError| match err {
-                sqlxError::RowNotFound => errors::Find::NotFound,
-                _ => errors::Find::Other(err.into()),
-            })
-            .map(|record| User::build_from_repository(from_pg_uuid(record.id), record.nickname))
+            .map_err(|err| errors::List::Other(err.into()))?;
+
+        list(&mut conn, after, limit).await
     }
 
     #[tracing::instrument]
     async fn save(&self, user: &User) -> Result<(), errors::Save> {
-        let executor = &self
+        let mut transaction = self
             .db_service
-            .executor()
+            .pool()
+            .begin()
             .await
             .map_err(|err| errors::Save::Other(err.into()))?;
 
-        sqlx::query!(
-            "INSERT INTO users (id, nickname) VALUES ($1, $2)",
-            to_pg_uuid(user.id()),
-            user.nickname()
-        )
-        .execute(executor)
-        .await
-        .map_err(|err: sqlxError| match err {
-            sqlxError::Database(db_err)
-                if Some("users_nickname_unique")
-                    == db_err.downcast_ref::<PgDatabaseError>().constraint() =>
-            {
-                errors::Save::NicknameExists {
-                    nickname: user.nickname().into(),
-                }
-            }
-            err => errors::Save::Other(err.into()),
-        })
-        .and(Ok(()))
+        save(&mut transaction, user).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| errors::Save::Other(err.into()))
     }
+
+    #[tracing::instrument]
+    async fn update(&self, user: &User) -> Result<(), errors::Update> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Update::Other(err.into()))?;
+
+        update(&mut conn, user).await
+    }
+}
+
+/// Repository running its queries inside the transaction of a unit of work.
+pub struct TransactionRepository {
+    transaction: SharedTransaction,
+}
+
+impl TransactionRepository {
+    pub(crate) fn new(transaction: SharedTransaction) -> TransactionRepository {
+        TransactionRepository { transaction }
+    }
+}
+
+impl std::fmt::Debug for TransactionRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserTransactionRepository").finish()
+    }
+}
+
+#[async_trait]
+impl RepositoryInterface for TransactionRepository {
+    #[tracing::instrument]
+    async fn find(&self, id: &Id) -> Result<User, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find(conn, id, false).await
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<User, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find(conn, id, true).await
+    }
+
+    #[tracing::instrument]
+    async fn find_by_nickname(&self, nickname: &str) -> Result<User, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find_by_nickname(conn, nickname).await
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<User>, errors::List> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::List::Other(transaction_finished()))?;
+
+        list(conn, after, limit).await
+    }
+
+    #[tracing::instrument]
+    async fn save(&self, user: &User) -> Result<(), errors::Save> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Save::Other(transaction_finished()))?;
+
+        save(conn, user).await
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, user: &User) -> Result<(), errors::Update> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Update::Other(transaction_finished()))?;
+
+        update(conn, user).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DbRepository;
+    use crate::{
+        config::Config,
+        libs::db::service::{self, DBService},
+        user::contract::user_repository_contract,
+    };
+    use std::sync::Arc;
+
+    async fn repository() -> DbRepository {
+        let config = Config::load().unwrap().database.pool_config().unwrap();
+        let pool = service::connect(&config).await.unwrap();
+        DbRepository::new(Arc::new(DBService::new(pool)))
+    }
+
+    user_repository_contract!(
+        #[ignore = "needs the migrated Postgres database of database.url"]
+        repository
+    );
 }
@@ -0,0 +1,182 @@
+use crate::libs::memory::store::{lock, MemoryStoreInterface, Tables, UserRow};
+use async_trait::async_trait;
+use domain::{
+    libs::{id::Identifier, pagination::Page},
+    user::{errors, nickname, Id, RepositoryInterface, StoredUser, User},
+};
+use shaku::Provider;
+use std::{ops::Bound, sync::Arc};
+
+impl UserRow {
+    fn from_user(user: &User) -> UserRow {
+        UserRow {
+            id: user.id().inner_value(),
+            nickname: user.nickname().into(),
+            nickname_key: nickname::key(user.nickname()),
+            password_hash: user.password_hash().map(Into::into),
+            role: user.role(),
+            created_at: *user.created_at(),
+            updated_at: *user.updated_at(),
+            version: user.version(),
+            deleted_at: user.deleted_at().copied(),
+        }
+    }
+
+    fn to_user(&self) -> User {
+        User::build_from_repository(StoredUser {
+            id: Id::new(self.id),
+            nickname: self.nickname.clone(),
+            password_hash: self.password_hash.clone(),
+            role: self.role,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            version: self.version,
+            deleted_at: self.deleted_at,
+        })
+    }
+}
+
+fn find(tables: &Tables, id: &Id, include_deleted: bool) -> Result<User, errors::Find> {
+    tables
+        .users
+        .get(&id.inner_value())
+        .filter(|row| include_deleted || row.deleted_at.is_none())
+        .map(UserRow::to_user)
+        .ok_or(errors::Find::NotFound)
+}
+
+fn list(tables: &Tables, after: Option<&Id>, limit: usize) -> Page<User> {
+    let start = after.map_or(Bound::Unbounded, |id| Bound::Excluded(id.inner_value()));
+    let items = tables
+        .users
+        .range((start, Bound::Unbounded))
+        .map(|(_, row)| row)
+        .filter(|row| row.deleted_at.is_none())
+        .take(limit + 1)
+        .map(UserRow::to_user)
+        .collect();
+
+    Page::from_items(items, limit)
+}
+
+fn find_by_nickname(tables: &Tables, nickname: &str) -> Result<User, errors::Find> {
+    let key = nickname::key(nickname);
+    tables
+        .users
+        .values()
+        .find(|row| row.nickname_key == key && row.deleted_at.is_none())
+        .map(UserRow::to_user)
+        .ok_or(errors::Find::NotFound)
+}
+
+/// Like the unique constraint of the `users` table, soft deleted users keep their nickname.
+fn is_nickname_taken(tables: &Tables, row: &UserRow) -> bool {
+    tables
+        .users
+        .values()
+        .any(|other| other.id != row.id && other.nickname_key == row.nickname_key)
+}
+
+fn save(tables: &mut Tables, user: &User) -> Result<(), errors::Save> {
+    let row = UserRow::from_user(user);
+    if tables.users.contains_key(&row.id) {
+        return Err(errors::Save::Other(anyhow::anyhow!(
+            "User {} already exists",
+            user.id()
+        )));
+    }
+    if is_nickname_taken(tables, &row) {
+        return Err(errors::Save::NicknameExists {
+            nickname: row.nickname,
+        });
+    }
+
+    tables.users.insert(row.id, row);
+    tables.written();
+    Ok(())
+}
+
+fn update(tables: &mut Tables, user: &User) -> Result<(), errors::Update> {
+    let row = UserRow::from_user(user);
+    match tables.users.get(&row.id) {
+        None => return Err(errors::Update::NotFound),
+        Some(stored) if stored.version != user.loaded_version() => {
+            return Err(errors::Update::Conflict)
+        }
+        Some(_) => {}
+    }
+    if is_nickname_taken(tables, &row) {
+        return Err(errors::Update::NicknameExists {
+            nickname: row.nickname,
+        });
+    }
+
+    tables.users.insert(row.id, row);
+    tables.written();
+    Ok(())
+}
+
+#[derive(Provider)]
+#[shaku(interface = RepositoryInterface + Send + Sync)]
+pub struct MemoryRepository {
+    #[shaku(inject)]
+    store: Arc<dyn MemoryStoreInterface>,
+}
+
+impl MemoryRepository {
+    pub(crate) fn new(store: Arc<dyn MemoryStoreInterface>) -> MemoryRepository {
+        MemoryRepository { store }
+    }
+}
+
+impl std::fmt::Debug for MemoryRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserMemoryRepository").finish()
+    }
+}
+
+#[async_trait]
+impl RepositoryInterface for MemoryRepository {
+    #[tracing::instrument]
+    async fn find(&self, id: &Id) -> Result<User, errors::Find> {
+        find(&*lock(self.store.tables())?, id, false)
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<User, errors::Find> {
+        find(&*lock(self.store.tables())?, id, true)
+    }
+
+    #[tracing::instrument]
+    async fn find_by_nickname(&self, nickname: &str) -> Result<User, errors::Find> {
+        find_by_nickname(&*lock(self.store.tables())?, nickname)
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<User>, errors::List> {
+        Ok(list(&*lock(self.store.tables())?, after, limit))
+    }
+
+    #[tracing::instrument]
+    async fn save(&self, user: &User) -> Result<(), errors::Save> {
+        save(&mut *lock(self.store.tables())?, user)
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, user: &User) -> Result<(), errors::Update> {
+        update(&mut *lock(self.store.tables())?, user)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MemoryRepository;
+    use crate::{libs::memory::store::MemoryStore, user::contract::user_repository_contract};
+    use std::sync::Arc;
+
+    async fn repository() -> MemoryRepository {
+        MemoryRepository::new(Arc::new(MemoryStore::default()))
+    }
+
+    user_repository_contract!(repository);
+}
@@ -1 +1,9 @@
+#[cfg(feature = "postgres")]
 pub mod db_repository;
+#[cfg(any(test, feature = "memory"))]
+pub mod memory_repository;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_repository;
+
+#[cfg(test)]
+pub(crate) mod contract;
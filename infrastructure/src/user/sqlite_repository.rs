@@ -0,0 +1,397 @@
+use crate::{
+    libs::{
+        db::transaction_finished,
+        sqlite::{service::DBServiceInterface, unit_of_work::SharedTransaction},
+    },
+    outbox,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use domain::{
+    libs::{id::Identifier, pagination::Page},
+    user::{errors, nickname, Id, RepositoryInterface, StoredUser, User},
+};
+use shaku::Provider;
+use sqlx::{error::Error as sqlxError, sqlite::SqliteConnection, types::Uuid, FromRow};
+use std::sync::Arc;
+
+fn to_sqlite_id(id: &Id) -> String {
+    Uuid::from_u128(id.inner_value()).to_string()
+}
+
+fn from_sqlite_id(sqlite_id: &str) -> Result<Id, anyhow::Error> {
+    Ok(Id::new(Uuid::parse_str(sqlite_id)?.as_u128()))
+}
+
+#[derive(FromRow)]
+struct UserRecord {
+    id: String,
+    nickname: String,
+    password_hash: Option<String>,
+    role: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl UserRecord {
+    fn into_user(self) -> Result<User, anyhow::Error> {
+        Ok(User::build_from_repository(StoredUser {
+            id: from_sqlite_id(&self.id)?,
+            nickname: self.nickname,
+            password_hash: self.password_hash,
+            role: self.role.parse().map_err(anyhow::Error::msg)?,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            version: self.version,
+            deleted_at: self.deleted_at,
+        }))
+    }
+}
+
+async fn find(
+    conn: &mut SqliteConnection,
+    id: &Id,
+    include_deleted: bool,
+) -> Result<User, errors::Find> {
+    sqlx::query_as::<_, UserRecord>(
+        r#"SELECT id, nickname, password_hash, role, created_at, updated_at, version, deleted_at
+           FROM users
+           WHERE id = ?1 AND (?2 OR deleted_at IS NULL)"#,
+    )
+    .bind(to_sqlite_id(id))
+    .bind(include_deleted)
+    .fetch_one(conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        sqlxError::RowNotFound => errors::Find::NotFound,
+        _ => errors::Find::Other(err.into()),
+    })?
+    .into_user()
+    .map_err(errors::Find::Other)
+}
+
+async fn list(
+    conn: &mut SqliteConnection,
+    after: Option<&Id>,
+    limit: usize,
+) -> Result<Page<User>, errors::List> {
+    let records = sqlx::query_as::<_, UserRecord>(
+        r#"SELECT id, nickname, password_hash, role, created_at, updated_at, version, deleted_at
+           FROM users
+           WHERE deleted_at IS NULL AND (?1 IS NULL OR id > ?1)
+           ORDER BY id
+           LIMIT ?2"#,
+    )
+    .bind(after.map(to_sqlite_id))
+    .bind(limit as i64 + 1)
+    .fetch_all(conn)
+    .await
+    .map_err(|err| errors::List::Other(err.into()))?;
+
+    let items = records
+        .into_iter()
+        .map(UserRecord::into_user)
+        .collect::<Result<Vec<User>, anyhow::Error>>()
+        .map_err(errors::List::Other)?;
+
+    Ok(Page::from_items(items, limit))
+}
+
+async fn find_by_nickname(
+    conn: &mut SqliteConnection,
+    nickname: &str,
+) -> Result<User, errors::Find> {
+    sqlx::query_as::<_, UserRecord>(
+        r#"SELECT id, nickname, password_hash, role, created_at, updated_at, version, deleted_at
+           FROM users
+           WHERE nickname_key = ?1 AND deleted_at IS NULL"#,
+    )
+    .bind(nickname::key(nickname))
+    .fetch_one(conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        sqlxError::RowNotFound => errors::Find::NotFound,
+        _ => errors::Find::Other(err.into()),
+    })?
+    .into_user()
+    .map_err(errors::Find::Other)
+}
+
+async fn exists(conn: &mut SqliteConnection, id: &Id) -> Result<bool, sqlxError> {
+    sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM users WHERE id = ?1)")
+        .bind(to_sqlite_id(id))
+        .fetch_one(conn)
+        .await
+}
+
+/// SQLite only names the columns of the violated constraint in its message.
+fn is_nickname_taken(err: &sqlxError) -> bool {
+    match err {
+        sqlxError::Database(db_err) => db_err
+            .message()
+            .contains("UNIQUE constraint failed: users.nickname_key"),
+        _ => false,
+    }
+}
+
+async fn save(conn: &mut SqliteConnection, user: &User) -> Result<(), errors::Save> {
+    sqlx::query(
+        r#"INSERT INTO users
+           (id, nickname, nickname_key, password_hash, role, created_at, updated_at, version)
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"#,
+    )
+    .bind(to_sqlite_id(user.id()))
+    .bind(user.nickname())
+    .bind(nickname::key(user.nickname()))
+    .bind(user.password_hash())
+    .bind(user.role().as_str())
+    .bind(user.created_at())
+    .bind(user.updated_at())
+    .bind(user.version())
+    .execute(&mut *conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        err if is_nickname_taken(&err) => errors::Save::NicknameExists {
+            nickname: user.nickname().into(),
+        },
+        err => errors::Save::Other(err.into()),
+    })?;
+
+    outbox::record_sqlite(conn, user.events())
+        .await
+        .map_err(|err| errors::Save::Other(err.into()))
+}
+
+async fn update(conn: &mut SqliteConnection, user: &User) -> Result<(), errors::Update> {
+    let done = sqlx::query(
+        r#"UPDATE users
+           SET nickname = ?2, nickname_key = ?3, password_hash = ?4, role = ?5, updated_at = ?6,
+               version = ?7, deleted_at = ?8
+           WHERE id = ?1 AND version = ?9"#,
+    )
+    .bind(to_sqlite_id(user.id()))
+    .bind(user.nickname())
+    .bind(nickname::key(user.nickname()))
+    .bind(user.password_hash())
+    .bind(user.role().as_str())
+    .bind(user.updated_at())
+    .bind(user.version())
+    .bind(user.deleted_at())
+    .bind(user.loaded_version())
+    .execute(&mut *conn)
+    .await
+    .map_err(|err: sqlxError| match err {
+        err if is_nickname_taken(&err) => errors::Update::NicknameExists {
+            nickname: user.nickname().into(),
+        },
+        err => errors::Update::Other(err.into()),
+    })?;
+
+    if done.rows_affected() > 0 {
+        return Ok(());
+    }
+
+    // Nothing was updated: either the user is gone or another writer bumped its version.
+    match exists(conn, user.id()).await {
+        Ok(true) => Err(errors::Update::Conflict),
+        Ok(false) => Err(errors::Update::NotFound),
+        Err(err) => Err(errors::Update::Other(err.into())),
+    }
+}
+
+#[derive(Provider)]
+#[shaku(interface = RepositoryInterface + Send + Sync)]
+pub struct DbRepository {
+    #[shaku(inject)]
+    db_service: Arc<dyn DBServiceInterface>,
+}
+
+impl DbRepository {
+    pub fn new(db_service: Arc<dyn DBServiceInterface>) -> DbRepository {
+        DbRepository { db_service }
+    }
+}
+
+impl std::fmt::Debug for DbRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserSqliteRepository").finish()
+    }
+}
+
+#[async_trait]
+impl RepositoryInterface for DbRepository {
+    #[tracing::instrument]
+    async fn find(&self, id: &Id) -> Result<User, errors::Find> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Find::Other(err.into()))?;
+
+        find(&mut conn, id, false).await
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<User, errors::Find> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Find::Other(err.into()))?;
+
+        find(&mut conn, id, true).await
+    }
+
+    #[tracing::instrument]
+    async fn find_by_nickname(&self, nickname: &str) -> Result<User, errors::Find> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Find::Other(err.into()))?;
+
+        find_by_nickname(&mut conn, nickname).await
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<User>, errors::List> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::List::Other(err.into()))?;
+
+        list(&mut conn, after, limit).await
+    }
+
+    #[tracing::instrument]
+    async fn save(&self, user: &User) -> Result<(), errors::Save> {
+        let mut transaction = self
+            .db_service
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| errors::Save::Other(err.into()))?;
+
+        save(&mut transaction, user).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|err| errors::Save::Other(err.into()))
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, user: &User) -> Result<(), errors::Update> {
+        let mut conn = self
+            .db_service
+            .pool()
+            .acquire()
+            .await
+            .map_err(|err| errors::Update::Other(err.into()))?;
+
+        update(&mut conn, user).await
+    }
+}
+
+/// Repository running its queries inside the transaction of a unit of work.
+pub struct TransactionRepository {
+    transaction: SharedTransaction,
+}
+
+impl TransactionRepository {
+    pub(crate) fn new(transaction: SharedTransaction) -> TransactionRepository {
+        TransactionRepository { transaction }
+    }
+}
+
+impl std::fmt::Debug for TransactionRepository {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserSqliteTransactionRepository").finish()
+    }
+}
+
+#[async_trait]
+impl RepositoryInterface for TransactionRepository {
+    #[tracing::instrument]
+    async fn find(&self, id: &Id) -> Result<User, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find(conn, id, false).await
+    }
+
+    #[tracing::instrument]
+    async fn find_including_deleted(&self, id: &Id) -> Result<User, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find(conn, id, true).await
+    }
+
+    #[tracing::instrument]
+    async fn find_by_nickname(&self, nickname: &str) -> Result<User, errors::Find> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Find::Other(transaction_finished()))?;
+
+        find_by_nickname(conn, nickname).await
+    }
+
+    #[tracing::instrument]
+    async fn list(&self, after: Option<&Id>, limit: usize) -> Result<Page<User>, errors::List> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::List::Other(transaction_finished()))?;
+
+        list(conn, after, limit).await
+    }
+
+    #[tracing::instrument]
+    async fn save(&self, user: &User) -> Result<(), errors::Save> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Save::Other(transaction_finished()))?;
+
+        save(conn, user).await
+    }
+
+    #[tracing::instrument]
+    async fn update(&self, user: &User) -> Result<(), errors::Update> {
+        let mut transaction = self.transaction.lock().await;
+        let conn = transaction
+            .as_mut()
+            .ok_or_else(|| errors::Update::Other(transaction_finished()))?;
+
+        update(conn, user).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DbRepository;
+    use crate::{
+        libs::sqlite::service::{connect_in_memory, DBService},
+        user::contract::user_repository_contract,
+    };
+    use std::sync::Arc;
+
+    async fn repository() -> DbRepository {
+        DbRepository::new(Arc::new(DBService::new(connect_in_memory().await)))
+    }
+
+    user_repository_contract!(repository);
+}
@@ -0,0 +1,122 @@
+use crate::{
+    errors::{self, IntoGraphqlError},
+    provide,
+};
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    error::ErrorUnauthorized,
+    http::header::AUTHORIZATION,
+    Error, HttpMessage,
+};
+use async_graphql::Context;
+use domain::{
+    auth::TokenServiceInterface,
+    policies::Actor,
+    user::{Id as UserId, RepositoryInterface as UserRepositoryInterface},
+};
+use futures::future::{err, ok, LocalBoxFuture, Ready};
+use std::{
+    sync::Arc,
+    task::{Context as TaskContext, Poll},
+};
+
+/// The user authenticated by the bearer token of the request.
+#[derive(Clone, Copy, Debug)]
+pub struct CurrentUser {
+    pub id: UserId,
+}
+
+/// Get the authenticated user of a GraphQL request, failing when the caller is anonymous.
+pub fn current_user<'a>(ctx: &'a Context<'_>) -> async_graphql::Result<&'a CurrentUser> {
+    ctx.data_opt::<CurrentUser>()
+        .ok_or_else(|| errors::unauthenticated("Authentication required"))
+}
+
+/// Load the authenticated user of a GraphQL request to check what they are allowed to do.
+pub async fn actor(ctx: &Context<'_>) -> async_graphql::Result<Actor> {
+    let current_user = current_user(ctx)?;
+    let user_repository: Box<dyn UserRepositoryInterface + Send + Sync> = provide(ctx)?;
+
+    let user = user_repository
+        .find(&current_user.id)
+        .await
+        .map_err(IntoGraphqlError::into_graphql_error)?;
+    Ok(Actor::from_user(&user))
+}
+
+fn bearer_token(req: &ServiceRequest) -> Option<&str> {
+    req.headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Resolve the `Authorization: Bearer <token>` header into a `CurrentUser` stored in the
+/// request extensions, requests with an invalid token are rejected.
+pub struct Authentication {
+    token_service: Arc<dyn TokenServiceInterface>,
+}
+
+impl Authentication {
+    pub fn new(token_service: Arc<dyn TokenServiceInterface>) -> Authentication {
+        Authentication { token_service }
+    }
+}
+
+impl<S, B> Transform<S> for Authentication
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AuthenticationMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(AuthenticationMiddleware {
+            service,
+            token_service: self.token_service.clone(),
+        })
+    }
+}
+
+pub struct AuthenticationMiddleware<S> {
+    service: S,
+    token_service: Arc<dyn TokenServiceInterface>,
+}
+
+impl<S, B> Service for AuthenticationMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        let current_user = match bearer_token(&req).map(|token| self.token_service.verify(token)) {
+            None => None,
+            Some(Ok(id)) => Some(CurrentUser { id }),
+            Some(Err(error)) => {
+                tracing::info!(error = %error, "Request rejected");
+                return Box::pin(err(ErrorUnauthorized(error.to_string())));
+            }
+        };
+
+        if let Some(current_user) = current_user {
+            req.extensions_mut().insert(current_user);
+        }
+        Box::pin(self.service.call(req))
+    }
+}
@@ -0,0 +1,256 @@
+//! Turn the domain errors into GraphQL errors with a stable `extensions.code` clients can rely on,
+//! the message being meant for humans only.
+
+use async_graphql::{Error, ErrorExtensions};
+use domain::{
+    auth,
+    post::errors as post,
+    use_cases::{
+        create_post::errors::Create as CreatePost, create_user::errors::Create as CreateUser,
+        delete_post::errors::Delete as DeletePost, delete_user::errors::Delete as DeleteUser,
+        edit_post::errors::Edit as EditPost, rename_user::errors::Rename as RenameUser,
+        restore_post::errors::Restore as RestorePost, restore_user::errors::Restore as RestoreUser,
+        sign_in::errors::SignIn, sign_up::errors::SignUp,
+    },
+    user::errors as user,
+};
+use std::fmt::Display;
+
+fn with_code(err: impl Display, code: &'static str) -> Error {
+    Error::new(err.to_string()).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+/// The caller is authenticated but not allowed to perform the operation.
+pub fn forbidden(err: impl Display) -> Error {
+    with_code(err, "FORBIDDEN")
+}
+
+/// An argument failed validation, `field` names the offending argument.
+pub fn invalid_field(field: &'static str, err: impl Display) -> Error {
+    Error::new(err.to_string()).extend_with(|_, extensions| {
+        extensions.set("code", "BAD_USER_INPUT");
+        extensions.set("field", field);
+    })
+}
+
+/// The entity changed since the version the caller based their change on.
+pub fn conflict(err: impl Display) -> Error {
+    with_code(err, "CONFLICT")
+}
+
+/// The entity does not exist, or not anymore.
+pub fn not_found(err: impl Display) -> Error {
+    with_code(err, "NOT_FOUND")
+}
+
+/// Another user already has this nickname.
+pub fn nickname_taken(err: impl Display) -> Error {
+    with_code(err, "NICKNAME_TAKEN")
+}
+
+/// The credentials or the token of the caller are not valid.
+pub fn unauthenticated(err: impl Display) -> Error {
+    with_code(err, "UNAUTHENTICATED")
+}
+
+/// A failure the client can do nothing about, its details are logged and not sent back.
+pub fn internal(err: impl Display) -> Error {
+    let details = format!("{:#}", err);
+    tracing::error!(error = %details, "Internal error");
+    with_code("Internal server error", "INTERNAL")
+}
+
+/// Conversion of the domain errors, to be used instead of `?` which would expose every message.
+pub trait IntoGraphqlError {
+    fn into_graphql_error(self) -> Error;
+}
+
+impl IntoGraphqlError for user::Find {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            user::Find::NotFound => not_found(self),
+            user::Find::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for user::List {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            user::List::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for post::Find {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            post::Find::NotFound => not_found(self),
+            post::Find::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for post::List {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            post::List::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for auth::errors::Token {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            auth::errors::Token::Invalid => unauthenticated(self),
+            auth::errors::Token::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for SignUp {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            SignUp::InvalidNickname(_) => invalid_field("nickname", self),
+            SignUp::PasswordTooShort { .. } => invalid_field("password", self),
+            SignUp::NicknameExists { .. } => nickname_taken(self),
+            SignUp::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for SignIn {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            SignIn::InvalidCredentials => unauthenticated(self),
+            SignIn::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for CreateUser {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            CreateUser::InvalidNickname(_) => invalid_field("nickname", self),
+            CreateUser::NicknameExists { .. } => nickname_taken(self),
+            CreateUser::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for CreatePost {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            CreatePost::AuthorNotFound => not_found(self),
+            CreatePost::InvalidContent(_) => invalid_field("content", self),
+            CreatePost::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for RenameUser {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            RenameUser::NotFound => not_found(self),
+            RenameUser::InvalidNickname(_) => invalid_field("nickname", self),
+            RenameUser::NicknameExists { .. } => nickname_taken(self),
+            RenameUser::Conflict => conflict(self),
+            RenameUser::Forbidden(_) => forbidden(self),
+            RenameUser::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for EditPost {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            EditPost::NotFound => not_found(self),
+            EditPost::InvalidContent(_) => invalid_field("content", self),
+            EditPost::Conflict => conflict(self),
+            EditPost::Forbidden(_) => forbidden(self),
+            EditPost::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for DeletePost {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            DeletePost::NotFound => not_found(self),
+            DeletePost::Conflict => conflict(self),
+            DeletePost::Forbidden(_) => forbidden(self),
+            DeletePost::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for DeleteUser {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            DeleteUser::NotFound => not_found(self),
+            DeleteUser::Conflict => conflict(self),
+            DeleteUser::Forbidden(_) => forbidden(self),
+            DeleteUser::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for RestoreUser {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            RestoreUser::NotFound => not_found(self),
+            RestoreUser::Conflict => conflict(self),
+            RestoreUser::Forbidden(_) => forbidden(self),
+            RestoreUser::Other(err) => internal(err),
+        }
+    }
+}
+
+impl IntoGraphqlError for RestorePost {
+    fn into_graphql_error(self) -> Error {
+        match self {
+            RestorePost::NotFound => not_found(self),
+            RestorePost::Conflict => conflict(self),
+            RestorePost::Forbidden(_) => forbidden(self),
+            RestorePost::Other(err) => internal(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IntoGraphqlError;
+    use async_graphql::ErrorExtensionValues;
+    use domain::{use_cases::sign_up::errors::SignUp, user::errors::Find};
+
+    fn code(code: &str) -> Option<ErrorExtensionValues> {
+        let mut extensions = ErrorExtensionValues::default();
+        extensions.set("code", code);
+        Some(extensions)
+    }
+
+    #[test]
+    fn it_hides_the_details_of_internal_errors() {
+        let err = Find::Other(anyhow::anyhow!("connection refused to 10.0.0.3:5432"))
+            .into_graphql_error();
+        assert_eq!(err.message, "Internal server error");
+        assert_eq!(err.extensions, code("INTERNAL"));
+    }
+
+    #[test]
+    fn it_keeps_the_message_of_the_errors_clients_can_act_on() {
+        let err = SignUp::NicknameExists {
+            nickname: "Elliot".into(),
+        }
+        .into_graphql_error();
+        assert_eq!(err.message, "Nickname \"Elliot\" already exists");
+        assert_eq!(err.extensions, code("NICKNAME_TAKEN"));
+    }
+
+    #[test]
+    fn it_tells_when_an_entity_is_not_found() {
+        let err = Find::NotFound.into_graphql_error();
+        assert_eq!(err.message, Find::NotFound.to_string());
+        assert_eq!(err.extensions, code("NOT_FOUND"));
+    }
+}
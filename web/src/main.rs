@@ -2,25 +2,58 @@ use actix_web::{guard, web, App, HttpRequest, HttpResponse, HttpServer, Result};
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
 use async_graphql::Schema;
 use async_graphql_actix_web::{Request, Response, WSSubscription};
+use auth::{actor, current_user, Authentication, CurrentUser};
 use domain::{
-    post::Post,
+    auth::{Session, TokenServiceInterface},
+    events::{DispatcherInterface, DomainEvent},
+    post::{
+        Id as PostId, Post, RendererInterface as PostRendererInterface,
+        RepositoryInterface as PostRepositoryInterface,
+    },
+    unit_of_work::UnitOfWorkInterface,
     use_cases::create_post::{Interface as CreatePostUseCase, UseCase as CreatePostUseCaseImpl},
     use_cases::create_user::{Interface as CreateUserUseCase, UseCase as CreateUserUseCaseImpl},
-    user::RepositoryInterface as UserRepositoryInterface,
+    use_cases::delete_post::{Interface as DeletePostUseCase, UseCase as DeletePostUseCaseImpl},
+    use_cases::delete_user::{Interface as DeleteUserUseCase, UseCase as DeleteUserUseCaseImpl},
+    use_cases::edit_post::{Interface as EditPostUseCase, UseCase as EditPostUseCaseImpl},
+    use_cases::rename_user::{Interface as RenameUserUseCase, UseCase as RenameUserUseCaseImpl},
+    use_cases::restore_post::{Interface as RestorePostUseCase, UseCase as RestorePostUseCaseImpl},
+    use_cases::restore_user::{Interface as RestoreUserUseCase, UseCase as RestoreUserUseCaseImpl},
+    use_cases::sign_in::{Interface as SignInUseCase, UseCase as SignInUseCaseImpl},
+    use_cases::sign_up::{Interface as SignUpUseCase, UseCase as SignUpUseCaseImpl},
+    user::{errors::Find as UserFindError, RepositoryInterface as UserRepositoryInterface},
     user::{Id as UserId, User},
 };
+use errors::IntoGraphqlError;
 use infrastructure::{
-    libs::db::service::DBService, post::db_repository::DbRepository as PostDbRepository,
-    user::db_repository::DbRepository as UserDbRepository,
+    auth::{
+        argon2_hasher::Argon2PasswordHasher,
+        jwt_token_service::{JwtTokenService, JwtTokenServiceParameters},
+    },
+    backend::{self, PostRepository, Store, UnitOfWork, UserRepository},
+    config::Config,
+    events::{
+        broadcast_handler::BroadcastHandler, dispatcher::InProcessDispatcher,
+        logging_handler::LoggingHandler,
+    },
+    libs::clock::SystemClock,
+    outbox::LoggingSink,
+    post::markdown_renderer::MarkdownRenderer,
 };
-use shaku::{module, HasProvider};
-use telemetry::{get_subscriber, init_subscriber};
+use shaku::{module, HasComponent, HasProvider};
+use std::{process::exit, sync::Arc};
 use tracing_actix_web::TracingLogger;
 
+mod auth;
+mod errors;
 mod telemetry;
 
-async fn index(schema: web::Data<GqlSchema>, req: Request) -> Response {
-    schema.execute(req.into_inner()).await.into()
+async fn index(schema: web::Data<GqlSchema>, http_req: HttpRequest, req: Request) -> Response {
+    let mut request = req.into_inner();
+    if let Some(current_user) = http_req.extensions().get::<CurrentUser>() {
+        request = request.data(*current_user);
+    }
+    schema.execute(request).await.into()
 }
 
 async fn index_playground() -> Result<HttpResponse> {
@@ -42,30 +75,127 @@ async fn index_ws(
 // Module
 module! {
     AppModule {
-        components = [],
+        components = [
+            Store,
+            InProcessDispatcher,
+            Argon2PasswordHasher,
+            JwtTokenService,
+            MarkdownRenderer,
+            SystemClock,
+        ],
         providers = [
-            UserDbRepository,
-            PostDbRepository,
+            UserRepository,
+            PostRepository,
+            SignUpUseCaseImpl,
+            SignInUseCaseImpl,
             CreateUserUseCaseImpl,
             CreatePostUseCaseImpl,
-            DBService,
+            RenameUserUseCaseImpl,
+            EditPostUseCaseImpl,
+            DeletePostUseCaseImpl,
+            DeleteUserUseCaseImpl,
+            RestoreUserUseCaseImpl,
+            RestorePostUseCaseImpl,
+            UnitOfWork,
         ]
     }
 }
 
+/// Provide an interface of the module to a resolver, a provider failure fails the request.
+pub fn provide<I: ?Sized + 'static>(ctx: &Context<'_>) -> async_graphql::Result<Box<I>>
+where
+    AppModule: HasProvider<I>,
+{
+    ctx.data_unchecked::<AppModule>()
+        .provide()
+        .map_err(errors::internal)
+}
+
+/// Provide every interface of the module once, so that a misconfiguration stops the server at
+/// startup rather than failing the requests. Components are already built with the module.
+fn check_module(module: &AppModule) -> Result<(), String> {
+    fn check<I: ?Sized + 'static>(module: &AppModule) -> Result<(), String>
+    where
+        AppModule: HasProvider<I>,
+    {
+        HasProvider::<I>::provide(module)
+            .map(|_| ())
+            .map_err(|err| format!("Cannot provide {}: {}", std::any::type_name::<I>(), err))
+    }
+
+    check::<dyn UserRepositoryInterface + Send + Sync>(module)?;
+    check::<dyn PostRepositoryInterface + Send + Sync>(module)?;
+    check::<dyn UnitOfWorkInterface + Send + Sync>(module)?;
+    check::<dyn SignUpUseCase>(module)?;
+    check::<dyn SignInUseCase>(module)?;
+    check::<dyn CreateUserUseCase>(module)?;
+    check::<dyn CreatePostUseCase>(module)?;
+    check::<dyn RenameUserUseCase>(module)?;
+    check::<dyn EditPostUseCase>(module)?;
+    check::<dyn DeletePostUseCase>(module)?;
+    check::<dyn DeleteUserUseCase>(module)?;
+    check::<dyn RestoreUserUseCase>(module)?;
+    check::<dyn RestorePostUseCase>(module)
+}
+
 #[actix_rt::main]
 async fn main() -> std::io::Result<()> {
-    let subscriber = get_subscriber("web".into(), "info".into());
-    init_subscriber(subscriber);
+    // Validated before anything else so that a bad setting is reported on its own.
+    let (config, token_config) = match Config::load().and_then(|config| {
+        backend::check_config(&config.database)?;
+        let token_config = config.auth.token_config()?;
+        Ok((config, token_config))
+    }) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            exit(1);
+        }
+    };
+    telemetry::init_from_config("web".into(), &config.log);
+
+    let store = match backend::connect(&config.database).await {
+        Ok(store) => store,
+        Err(err) => {
+            eprintln!("Error: {:#}", err);
+            exit(1);
+        }
+    };
+    let relay = backend::relay(&store, Arc::new(LoggingSink));
+    let module = AppModule::builder()
+        .with_component_parameters::<Store>(store)
+        .with_component_parameters::<JwtTokenService>(JwtTokenServiceParameters {
+            secret: token_config.secret,
+            ttl: token_config.ttl,
+        })
+        .build();
+    if let Err(err) = check_module(&module) {
+        eprintln!("Error: {}", err);
+        exit(1);
+    }
+    let token_service: Arc<dyn TokenServiceInterface> = module.resolve();
+    let dispatcher: &dyn DispatcherInterface = module.resolve_ref();
+    dispatcher.subscribe(Arc::new(LoggingHandler));
+    let broadcast_handler = Arc::new(BroadcastHandler::new(1024));
+    dispatcher.subscribe(broadcast_handler.clone());
 
     let schema = Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
-        .data(AppModule::builder().build())
+        .data(module)
+        .data(broadcast_handler)
+        .limit_depth(config.graphql.max_depth)
+        .limit_complexity(config.graphql.max_complexity)
         .finish();
 
-    println!("Playground: http://localhost:8000");
+    if let Some(relay) = relay {
+        actix_rt::spawn(relay);
+    }
+
+    let address = config.server.address();
+    println!("Playground: http://{}", address);
 
     HttpServer::new(move || {
         App::new()
+            .wrap(Authentication::new(token_service.clone()))
             .wrap(TracingLogger)
             .data(schema.clone())
             .service(web::resource("/").guard(guard::Post()).to(index))
@@ -77,21 +207,40 @@ async fn main() -> std::io::Result<()> {
             )
             .service(web::resource("/").guard(guard::Get()).to(index_playground))
     })
-    .bind("127.0.0.1:8000")?
+    .bind(address)?
     .run()
     .await
 }
 
-use async_graphql::{Context, Object, Subscription, ID};
-use futures::{Stream, StreamExt};
-use std::time::Duration;
+use async_graphql::connection::{query, Connection, Edge, EmptyFields};
+use async_graphql::{Context, Object, SimpleObject, Subscription, ID};
+use chrono::{DateTime, Utc};
+use futures::{future, Stream, StreamExt};
 
 pub type GqlSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
 
+const DEFAULT_PAGE_SIZE: usize = 20;
+const MAX_PAGE_SIZE: usize = 100;
+
+/// Only forward pagination is supported since listings use keyset pagination on ids.
+fn page_size(
+    before: &Option<String>,
+    first: Option<usize>,
+    last: Option<usize>,
+) -> async_graphql::Result<usize> {
+    if before.is_some() || last.is_some() {
+        return Err("Backward pagination is not supported".into());
+    }
+    Ok(first.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE))
+}
+
 #[derive(Clone)]
 pub struct GraphUser {
     id: ID,
     nickname: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
 }
 
 #[Object]
@@ -103,6 +252,19 @@ impl GraphUser {
     async fn nickname(&self) -> &str {
         &self.nickname
     }
+
+    async fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    async fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    /// Pass it back when changing the entity to detect concurrent changes.
+    async fn version(&self) -> i32 {
+        self.version
+    }
 }
 
 impl GraphUser {
@@ -110,6 +272,24 @@ impl GraphUser {
         GraphUser {
             id: ID(user.id().to_string()),
             nickname: user.nickname().to_owned(),
+            created_at: *user.created_at(),
+            updated_at: *user.updated_at(),
+            version: user.version(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct GraphAuthPayload {
+    token: String,
+    user: GraphUser,
+}
+
+impl GraphAuthPayload {
+    fn build(session: Session) -> GraphAuthPayload {
+        GraphAuthPayload {
+            token: session.token,
+            user: GraphUser::build(session.user),
         }
     }
 }
@@ -117,7 +297,11 @@ impl GraphUser {
 #[derive(Clone)]
 pub struct GraphPost {
     id: ID,
+    author_id: UserId,
     content: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    version: i32,
 }
 
 #[Object]
@@ -126,16 +310,59 @@ impl GraphPost {
         &self.id
     }
 
+    /// The Markdown source of the post.
     async fn content(&self) -> &str {
         &self.content
     }
+
+    /// The post rendered to sanitized HTML.
+    async fn content_html(&self, ctx: &Context<'_>) -> String {
+        let module = ctx.data_unchecked::<AppModule>();
+        let renderer: &dyn PostRendererInterface = module.resolve_ref();
+        renderer.render_html(&self.content)
+    }
+
+    async fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    async fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    /// Pass it back when changing the entity to detect concurrent changes.
+    async fn version(&self) -> i32 {
+        self.version
+    }
+
+    #[tracing::instrument(skip(self, ctx))]
+    async fn author(&self, ctx: &Context<'_>) -> async_graphql::Result<GraphUser> {
+        let user_repository: Box<dyn UserRepositoryInterface + Send + Sync> = provide(ctx)?;
+        self.find_author(&*user_repository).await
+    }
 }
 
 impl GraphPost {
+    /// The author is found even once deleted, their posts are still listed.
+    async fn find_author(
+        &self,
+        users: &dyn UserRepositoryInterface,
+    ) -> async_graphql::Result<GraphUser> {
+        users
+            .find_including_deleted(&self.author_id)
+            .await
+            .map(GraphUser::build)
+            .map_err(IntoGraphqlError::into_graphql_error)
+    }
+
     fn build(post: Post) -> GraphPost {
         GraphPost {
             id: ID(post.id().to_string()),
+            author_id: *post.author_id(),
             content: post.content().to_owned(),
+            created_at: *post.created_at(),
+            updated_at: *post.updated_at(),
+            version: post.version(),
         }
     }
 }
@@ -152,16 +379,12 @@ impl QueryRoot {
         #[graphql(desc = "Get a user")] id: ID,
     ) -> async_graphql::Result<Option<GraphUser>> {
         let user_id: UserId = id.parse()?;
-        let module = ctx.data_unchecked::<AppModule>();
-        let user_repository: Box<dyn UserRepositoryInterface + Send + Sync> =
-            module.provide().unwrap();
+        let user_repository: Box<dyn UserRepositoryInterface + Send + Sync> = provide(ctx)?;
 
-        let result = user_repository.find(&user_id).await;
-
-        if let Ok(user) = result {
-            Ok(Some(GraphUser::build(user)))
-        } else {
-            Ok(None)
+        match user_repository.find(&user_id).await {
+            Ok(user) => Ok(Some(GraphUser::build(user))),
+            Err(UserFindError::NotFound) => Ok(None),
+            Err(err) => Err(err.into_graphql_error()),
         }
     }
 
@@ -170,19 +393,87 @@ impl QueryRoot {
         &self,
         ctx: &Context<'_>,
         #[graphql(desc = "Search a user")] nickname: String,
-    ) -> Option<GraphUser> {
-        let module = ctx.data_unchecked::<AppModule>();
-        let user_repository: Box<dyn UserRepositoryInterface + Send + Sync> =
-            module.provide().unwrap();
-
-        let result = user_repository.find_by_nickname(&nickname).await;
+    ) -> async_graphql::Result<Option<GraphUser>> {
+        let user_repository: Box<dyn UserRepositoryInterface + Send + Sync> = provide(ctx)?;
 
-        if let Ok(user) = result {
-            Some(GraphUser::build(user))
-        } else {
-            None
+        match user_repository.find_by_nickname(&nickname).await {
+            Ok(user) => Ok(Some(GraphUser::build(user))),
+            Err(UserFindError::NotFound) => Ok(None),
+            Err(err) => Err(err.into_graphql_error()),
         }
     }
+
+    #[tracing::instrument(skip(ctx))]
+    async fn users(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> async_graphql::Result<Connection<String, GraphUser, EmptyFields, EmptyFields>> {
+        let user_repository: Box<dyn UserRepositoryInterface + Send + Sync> = provide(ctx)?;
+
+        query(
+            after,
+            before,
+            first,
+            last,
+            |after: Option<String>, before, first, last| async move {
+                let limit = page_size(&before, first, last)?;
+                let after: Option<UserId> = after.map(|cursor| cursor.parse()).transpose()?;
+                let page = user_repository
+                    .list(after.as_ref(), limit)
+                    .await
+                    .map_err(IntoGraphqlError::into_graphql_error)?;
+
+                let mut connection = Connection::new(after.is_some(), page.has_next_page);
+                connection.append(
+                    page.items
+                        .into_iter()
+                        .map(|user| Edge::new(user.id().to_string(), GraphUser::build(user))),
+                );
+                Ok::<_, async_graphql::Error>(connection)
+            },
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(ctx))]
+    async fn posts(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> async_graphql::Result<Connection<String, GraphPost, EmptyFields, EmptyFields>> {
+        let post_repository: Box<dyn PostRepositoryInterface + Send + Sync> = provide(ctx)?;
+
+        query(
+            after,
+            before,
+            first,
+            last,
+            |after: Option<String>, before, first, last| async move {
+                let limit = page_size(&before, first, last)?;
+                let after: Option<PostId> = after.map(|cursor| cursor.parse()).transpose()?;
+                let page = post_repository
+                    .list(after.as_ref(), limit)
+                    .await
+                    .map_err(IntoGraphqlError::into_graphql_error)?;
+
+                let mut connection = Connection::new(after.is_some(), page.has_next_page);
+                connection.append(
+                    page.items
+                        .into_iter()
+                        .map(|post| Edge::new(post.id().to_string(), GraphPost::build(post))),
+                );
+                Ok::<_, async_graphql::Error>(connection)
+            },
+        )
+        .await
+    }
 }
 
 #[derive(Debug)]
@@ -190,18 +481,50 @@ pub struct MutationRoot;
 
 #[Object]
 impl MutationRoot {
+    /// Create a user without a password, who cannot sign in.
+    #[graphql(deprecation = "Use signUp, which creates a user able to sign in")]
     #[tracing::instrument(skip(ctx))]
     async fn create_user(
         &self,
         ctx: &Context<'_>,
         nickname: String,
     ) -> async_graphql::Result<GraphUser> {
-        let module = ctx.data_unchecked::<AppModule>();
-        let create_user: Box<dyn CreateUserUseCase> = module.provide().unwrap();
-        Ok(create_user
+        let create_user: Box<dyn CreateUserUseCase> = provide(ctx)?;
+        create_user
             .call(&nickname)
             .await
-            .map(|user| GraphUser::build(user))?)
+            .map(|user| GraphUser::build(user))
+            .map_err(IntoGraphqlError::into_graphql_error)
+    }
+
+    #[tracing::instrument(skip(ctx, password))]
+    async fn sign_up(
+        &self,
+        ctx: &Context<'_>,
+        nickname: String,
+        password: String,
+    ) -> async_graphql::Result<GraphAuthPayload> {
+        let sign_up: Box<dyn SignUpUseCase> = provide(ctx)?;
+        sign_up
+            .call(&nickname, &password)
+            .await
+            .map(|session| GraphAuthPayload::build(session))
+            .map_err(IntoGraphqlError::into_graphql_error)
+    }
+
+    #[tracing::instrument(skip(ctx, password))]
+    async fn sign_in(
+        &self,
+        ctx: &Context<'_>,
+        nickname: String,
+        password: String,
+    ) -> async_graphql::Result<GraphAuthPayload> {
+        let sign_in: Box<dyn SignInUseCase> = provide(ctx)?;
+        sign_in
+            .call(&nickname, &password)
+            .await
+            .map(|session| GraphAuthPayload::build(session))
+            .map_err(IntoGraphqlError::into_graphql_error)
     }
 
     #[tracing::instrument(skip(ctx))]
@@ -210,12 +533,97 @@ impl MutationRoot {
         ctx: &Context<'_>,
         content: String,
     ) -> async_graphql::Result<GraphPost> {
-        let module = ctx.data_unchecked::<AppModule>();
-        let create_post: Box<dyn CreatePostUseCase> = module.provide().unwrap();
-        Ok(create_post
-            .call(&content)
+        let current_user = current_user(ctx)?;
+        let create_post: Box<dyn CreatePostUseCase> = provide(ctx)?;
+        create_post
+            .call(&current_user.id, &content)
+            .await
+            .map(|post| GraphPost::build(post))
+            .map_err(IntoGraphqlError::into_graphql_error)
+    }
+
+    #[tracing::instrument(skip(ctx))]
+    async fn rename_user(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        nickname: String,
+        #[graphql(desc = "Version of the user the change is based on")] version: Option<i32>,
+    ) -> async_graphql::Result<GraphUser> {
+        let user_id: UserId = id.parse()?;
+        let actor = actor(ctx).await?;
+        let rename_user: Box<dyn RenameUserUseCase> = provide(ctx)?;
+        rename_user
+            .call(&actor, &user_id, &nickname, version)
+            .await
+            .map(|user| GraphUser::build(user))
+            .map_err(IntoGraphqlError::into_graphql_error)
+    }
+
+    #[tracing::instrument(skip(ctx))]
+    async fn edit_post(
+        &self,
+        ctx: &Context<'_>,
+        id: ID,
+        content: String,
+        #[graphql(desc = "Version of the post the change is based on")] version: Option<i32>,
+    ) -> async_graphql::Result<GraphPost> {
+        let post_id: PostId = id.parse()?;
+        let actor = actor(ctx).await?;
+        let edit_post: Box<dyn EditPostUseCase> = provide(ctx)?;
+        edit_post
+            .call(&actor, &post_id, &content, version)
+            .await
+            .map(|post| GraphPost::build(post))
+            .map_err(IntoGraphqlError::into_graphql_error)
+    }
+
+    #[tracing::instrument(skip(ctx))]
+    async fn delete_post(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<bool> {
+        let post_id: PostId = id.parse()?;
+        let actor = actor(ctx).await?;
+        let delete_post: Box<dyn DeletePostUseCase> = provide(ctx)?;
+        delete_post
+            .call(&actor, &post_id)
+            .await
+            .map_err(IntoGraphqlError::into_graphql_error)?;
+        Ok(true)
+    }
+
+    #[tracing::instrument(skip(ctx))]
+    async fn delete_user(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<bool> {
+        let user_id: UserId = id.parse()?;
+        let actor = actor(ctx).await?;
+        let delete_user: Box<dyn DeleteUserUseCase> = provide(ctx)?;
+        delete_user
+            .call(&actor, &user_id)
             .await
-            .map(|post| GraphPost::build(post))?)
+            .map_err(IntoGraphqlError::into_graphql_error)?;
+        Ok(true)
+    }
+
+    #[tracing::instrument(skip(ctx))]
+    async fn restore_user(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<GraphUser> {
+        let user_id: UserId = id.parse()?;
+        let actor = actor(ctx).await?;
+        let restore_user: Box<dyn RestoreUserUseCase> = provide(ctx)?;
+        restore_user
+            .call(&actor, &user_id)
+            .await
+            .map(|user| GraphUser::build(user))
+            .map_err(IntoGraphqlError::into_graphql_error)
+    }
+
+    #[tracing::instrument(skip(ctx))]
+    async fn restore_post(&self, ctx: &Context<'_>, id: ID) -> async_graphql::Result<GraphPost> {
+        let post_id: PostId = id.parse()?;
+        let actor = actor(ctx).await?;
+        let restore_post: Box<dyn RestorePostUseCase> = provide(ctx)?;
+        restore_post
+            .call(&actor, &post_id)
+            .await
+            .map(|post| GraphPost::build(post))
+            .map_err(IntoGraphqlError::into_graphql_error)
     }
 }
 
@@ -223,11 +631,119 @@ pub struct SubscriptionRoot;
 
 #[Subscription]
 impl SubscriptionRoot {
-    async fn interval(&self, #[graphql(default = 1)] n: i32) -> impl Stream<Item = i32> {
-        let mut value = 0;
-        tokio::time::interval(Duration::from_secs(1)).map(move |_| {
-            value += n;
-            value
+    async fn post_created(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Only the posts of this author")] author_id: Option<ID>,
+    ) -> async_graphql::Result<impl Stream<Item = GraphPost>> {
+        let author_filter: Option<UserId> = author_id.map(|id| id.parse()).transpose()?;
+        let events = ctx.data_unchecked::<Arc<BroadcastHandler>>().events();
+
+        Ok(events.filter_map(move |event| {
+            future::ready(match event {
+                DomainEvent::PostCreated {
+                    id,
+                    author_id,
+                    content,
+                    created_at,
+                } if author_filter.map_or(true, |filter| filter == author_id) => Some(GraphPost {
+                    id: ID(id.to_string()),
+                    author_id,
+                    content,
+                    created_at,
+                    updated_at: created_at,
+                    version: 1,
+                }),
+                _ => None,
+            })
+        }))
+    }
+
+    async fn user_created(&self, ctx: &Context<'_>) -> impl Stream<Item = GraphUser> {
+        let events = ctx.data_unchecked::<Arc<BroadcastHandler>>().events();
+
+        events.filter_map(|event| {
+            future::ready(match event {
+                DomainEvent::UserCreated {
+                    id,
+                    nickname,
+                    created_at,
+                } => Some(GraphUser {
+                    id: ID(id.to_string()),
+                    nickname,
+                    created_at,
+                    updated_at: created_at,
+                    version: 1,
+                }),
+                _ => None,
+            })
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::GraphPost;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use domain::{
+        libs::{id::Identifier, pagination::Page},
+        post::Post,
+        user::{errors, Id, RepositoryInterface, Role, StoredUser, User},
+    };
+
+    /// A repository holding a single user, soft deleted.
+    struct DeletedUser(Id);
+
+    #[async_trait]
+    impl RepositoryInterface for DeletedUser {
+        async fn find(&self, _id: &Id) -> Result<User, errors::Find> {
+            Err(errors::Find::NotFound)
+        }
+
+        async fn find_including_deleted(&self, id: &Id) -> Result<User, errors::Find> {
+            if *id != self.0 {
+                return Err(errors::Find::NotFound);
+            }
+            Ok(User::build_from_repository(StoredUser {
+                id: self.0,
+                nickname: "Elliot".into(),
+                password_hash: None,
+                role: Role::Member,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 2,
+                deleted_at: Some(Utc::now()),
+            }))
+        }
+
+        async fn list(
+            &self,
+            _after: Option<&Id>,
+            _limit: usize,
+        ) -> Result<Page<User>, errors::List> {
+            Ok(Page::from_items(vec![], 0))
+        }
+
+        async fn find_by_nickname(&self, _nickname: &str) -> Result<User, errors::Find> {
+            Err(errors::Find::NotFound)
+        }
+
+        async fn save(&self, _user: &User) -> Result<(), errors::Save> {
+            Ok(())
+        }
+
+        async fn update(&self, _user: &User) -> Result<(), errors::Update> {
+            Err(errors::Update::NotFound)
+        }
+    }
+
+    #[tokio::test]
+    async fn it_finds_the_author_of_a_post_once_deleted() {
+        let author_id = Id::generate();
+        let post = GraphPost::build(Post::new(author_id, "My post".parse().unwrap(), Utc::now()));
+
+        let author = post.find_author(&DeletedUser(author_id)).await.unwrap();
+        assert_eq!(author.nickname, "Elliot");
+    }
+}
@@ -1,8 +1,9 @@
+use infrastructure::config::{LogConfig, LogFormat};
 use tracing::subscriber::set_global_default;
 use tracing::Subscriber;
 use tracing_bunyan_formatter::{BunyanFormattingLayer, JsonStorageLayer};
 use tracing_log::LogTracer;
-use tracing_subscriber::{layer::SubscriberExt, EnvFilter, Registry};
+use tracing_subscriber::{fmt, layer::SubscriberExt, EnvFilter, Registry};
 
 /// Compose multiple layers into a `tracing`'s subscriber.
 ///
@@ -20,6 +21,21 @@ pub fn get_subscriber(name: String, env_filter: String) -> impl Subscriber + Syn
         .with(formatting_layer)
 }
 
+/// Same layers as `get_subscriber` but logs human readable lines, for the terminal.
+pub fn get_pretty_subscriber(env_filter: String) -> impl Subscriber + Sync + Send {
+    let env_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(env_filter));
+    Registry::default().with(env_filter).with(fmt::layer())
+}
+
+/// Register the subscriber matching the log settings, `RUST_LOG` overrides their level.
+pub fn init_from_config(name: String, config: &LogConfig) {
+    match config.format {
+        LogFormat::Json => init_subscriber(get_subscriber(name, config.level.clone())),
+        LogFormat::Pretty => init_subscriber(get_pretty_subscriber(config.level.clone())),
+    }
+}
+
 /// Register a subscriber as global default to process span data.
 ///
 /// It should only be called once!